@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use eyre::{eyre, Result};
+use toml::Table;
+use crate::split_version;
+
+
+/// Canonical package names mapped to vendor-specific names
+///
+/// Vendors are keyed by lowercase name; a package without an alias for the
+/// vendor keeps its own name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aliases(HashMap<String, HashMap<String, String>>);
+
+impl Aliases {
+    /// Load `[aliases.<canonical>]` tables, each mapping vendor names to the
+    /// package name the vendor uses
+    pub fn load(&mut self, table: &Table) -> Result<()> {
+        for (canonical, vendors) in table.iter() {
+            let vendors = vendors.as_table().ok_or_else(|| eyre!("alias {} must be a table", canonical))?;
+            let entry = self.0.entry(canonical.to_owned()).or_default();
+            for (vendor, name) in vendors.iter() {
+                let name = name
+                    .as_str()
+                    .ok_or_else(|| eyre!("alias {}: {} must be a string", canonical, vendor))?;
+                entry.insert(vendor.to_lowercase(), name.to_owned());
+            }
+        }
+        Ok(())
+    }
+
+    /// The vendor-specific name of a canonical package, keeping any version
+    /// requirement such as `@18` or `>=3`
+    pub fn translate(&self, vendor: &str, package: &str) -> String {
+        let (name, requirement) = split_version(package);
+        self.0
+            .get(name)
+            .and_then(|vendors| vendors.get(&vendor.to_lowercase()))
+            .map(|name| format!("{}{}", name, requirement))
+            .unwrap_or_else(|| package.to_owned())
+    }
+
+    pub fn translate_all(&self, vendor: &str, packages: &[String]) -> Vec<String> {
+        packages.iter().map(|package| self.translate(vendor, package)).collect()
+    }
+
+    /// The canonical name of a vendor-specific package
+    pub fn canonical(&self, vendor: &str, package: &str) -> String {
+        let vendor = vendor.to_lowercase();
+        self.0
+            .iter()
+            .find(|(_, vendors)| vendors.get(&vendor).is_some_and(|name| name == package))
+            .map(|(canonical, _)| canonical.to_owned())
+            .unwrap_or_else(|| package.to_owned())
+    }
+}
+
+impl Default for Aliases {
+    fn default() -> Self {
+        let mut aliases: HashMap<String, HashMap<String, String>> = HashMap::new();
+        for (canonical, vendors) in ALIASES.iter() {
+            let entry = aliases.entry(canonical.to_string()).or_default();
+            for (vendor, name) in vendors.iter() {
+                entry.insert(vendor.to_string(), name.to_string());
+            }
+        }
+        Self(aliases)
+    }
+}
+
+//----------------------------------------------------------------------------//
+
+static ALIASES: &[(&str, &[(&str, &str)])] = &[
+    ("ag", &[
+        ("apt", "silversearcher-ag"),
+        ("brew", "the_silver_searcher"),
+        ("dnf", "the_silver_searcher"),
+        ("pacman", "the_silver_searcher"),
+        ("yay", "the_silver_searcher"),
+        ("yum", "the_silver_searcher"),
+        ("zypper", "the_silver_searcher"),
+        ("apk", "the_silver_searcher"),
+        ("xbps", "the_silver_searcher"),
+    ]),
+    ("build-essential", &[
+        ("apk", "build-base"),
+        ("pacman", "base-devel"),
+        ("yay", "base-devel"),
+        ("xbps", "base-devel"),
+    ]),
+    ("docker", &[
+        ("apt", "docker.io"),
+        ("dnf", "moby-engine"),
+    ]),
+    ("fd", &[
+        ("apt", "fd-find"),
+        ("dnf", "fd-find"),
+        ("yum", "fd-find"),
+    ]),
+    ("gvim", &[
+        ("apt", "vim-gtk3"),
+        ("dnf", "vim-X11"),
+        ("yum", "vim-X11"),
+        ("zypper", "gvim"),
+    ]),
+    ("netcat", &[
+        ("apt", "netcat-openbsd"),
+        ("apk", "netcat-openbsd"),
+        ("pacman", "openbsd-netcat"),
+        ("yay", "openbsd-netcat"),
+        ("dnf", "nmap-ncat"),
+        ("yum", "nmap-ncat"),
+    ]),
+    ("nodejs", &[
+        ("brew", "node"),
+        ("winget", "OpenJS.NodeJS"),
+    ]),
+    ("pip", &[
+        ("apt", "python3-pip"),
+        ("apk", "py3-pip"),
+        ("dnf", "python3-pip"),
+        ("yum", "python3-pip"),
+        ("eopkg", "pip"),
+        ("pacman", "python-pip"),
+        ("yay", "python-pip"),
+        ("xbps", "python3-pip"),
+        ("zypper", "python3-pip"),
+    ]),
+    ("python", &[
+        ("apt", "python3"),
+        ("apk", "python3"),
+        ("dnf", "python3"),
+        ("yum", "python3"),
+        ("xbps", "python3"),
+        ("zypper", "python3"),
+        ("winget", "Python.Python.3.12"),
+    ]),
+];
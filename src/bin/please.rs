@@ -1,27 +1,61 @@
-use std::{env, process::exit};
+use std::{env, path::Path, process::exit};
 
 use clap::Parser;
-use eyre::Result;
-use please_install::{Cmd, Params, PlsCommand, Vendor};
+use eyre::{eyre, Result};
+use please_install::{
+    merge_results, page, parse_results, pick, plan_files, plan_install, search_all, split_version,
+    Cmd, History, Holds, Manifest, Op, Package, PackageFile, PackageSet, Params, Plan, PlsCommand, Root, Transaction, VendorBackend,
+};
 use strum::IntoEnumIterator;
 
 
 fn main() -> Result<()> {
-    let params = Params::parse().config();
+    let params = Params::parse().config()?;
     let dry_run = params.dry_run;
     let assume_yes = params.yes;
-    let args = params.cmd.args();
+    let mut args = params.cmd.args();
+    let output = params.output.unwrap_or_default();
     let mut use_pager: Option<String> = None;
 
-    if params.cmd == Cmd::ListVendors {
-        for vendor in Vendor::iter() {
+    if let Cmd::ListVendors { verbose } = params.cmd {
+        for vendor in params.backends() {
             if vendor.is_available() {
-                println!("{:?}", vendor);
+                println!("{}", vendor.name());
+                if verbose {
+                    print_templates(vendor.as_ref());
+                }
             }
         }
         return Ok(())
     }
 
+    if let Cmd::History { package, since, until } = &params.cmd {
+        let history = History::load()?;
+        let transactions = history.filter(package.as_deref(), since.as_deref(), until.as_deref())?;
+        print_history(&transactions);
+        return Ok(())
+    }
+
+    if let Cmd::Undo { id } = params.cmd {
+        let history = History::load()?;
+        let transaction = match id {
+            Some(id) => history.get(id).ok_or_else(|| eyre!("no history entry #{}", id))?,
+            None => history.last().ok_or_else(|| eyre!("no history"))?,
+        };
+        let vendor = params
+            .backends()
+            .into_iter()
+            .find(|backend| backend.name() == transaction.vendor && backend.is_available())
+            .ok_or_else(|| eyre!("#{} was made by {}, which is not available", transaction.id, transaction.vendor))?;
+        let (cmd, packages) = transaction.inverse(vendor.as_ref(), &history)?;
+        let done = format!("{} {}", transaction.command, transaction.packages.join(" "));
+        let undo = format!("{} {}", cmd, packages.join(" "));
+        eprintln!("undo #{} ({} with {}): {}", transaction.id, done.trim_end(), transaction.vendor, undo.trim_end());
+        let su = params.elevation(vendor.as_ref(), cmd)?;
+        let status = vendor.execute(cmd, &packages, assume_yes, su, dry_run, None)?;
+        exit(status);
+    }
+
     #[cfg(not(target_os = "windows"))]
     if let Cmd::Search { pager, paginate, .. } = params.cmd.clone() {
         if paginate {
@@ -40,16 +74,389 @@ fn main() -> Result<()> {
         }
     }
 
+    #[cfg(not(target_os = "windows"))]
+    if let Cmd::Files { pager, paginate, .. } = params.cmd.clone() {
+        if paginate {
+            use_pager = pager
+                .or_else(|| env::var("PAGER").ok())
+                .or_else(|| Some("less".to_string()));
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    if let Cmd::Outdated { pager, paginate, .. } = params.cmd.clone() {
+        if paginate {
+            use_pager = pager
+                .or_else(|| env::var("PAGER").ok())
+                .or_else(|| Some("less".to_string()));
+        }
+    }
+
+    let picking = match &params.cmd {
+        Cmd::Search { args, all_vendors, pick: true, .. } => Some((args.to_owned(), *all_vendors)),
+        Cmd::Install { args, interactive: true } => Some((args.join(" "), false)),
+        _ => None,
+    };
+    if let Some((query, all_vendors)) = picking {
+        let backends: Vec<Box<dyn VendorBackend>> = if all_vendors {
+            params.backends().into_iter().filter(|backend| backend.is_available()).collect()
+        } else {
+            vec![params.backend()?]
+        };
+        let found: Vec<(String, Package)> = if all_vendors {
+            parse_results(&backends, &search_all(&backends, &query))
+        } else {
+            let vendor = &backends[0];
+            vendor
+                .packages(PlsCommand::Search, &[query.to_owned()])?
+                .into_iter()
+                .map(|package| (vendor.name(), package))
+                .collect()
+        };
+        if found.is_empty() {
+            eprintln!("no packages found for {}", query);
+            exit(1);
+        }
+        let picked = pick(&format!("{} ›", query), &describe(&found, all_vendors))?;
+        if picked.is_empty() {
+            eprintln!("nothing picked");
+            return Ok(());
+        }
+        let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+        for (vendor, package) in picked.into_iter().map(|index| &found[index]) {
+            match groups.iter_mut().find(|(name, _)| name == vendor) {
+                Some((_, packages)) => packages.push(package.name.to_owned()),
+                None => groups.push((vendor.to_owned(), vec![package.name.to_owned()])),
+            }
+        }
+        for (name, packages) in groups {
+            let Some(vendor) = backends.iter().find(|backend| backend.name() == name) else {
+                continue;
+            };
+            let su = params.elevation(vendor.as_ref(), PlsCommand::Install)?;
+            let status = vendor.execute(PlsCommand::Install, &packages, assume_yes, su, dry_run, None)?;
+            if status != 0 {
+                exit(status);
+            }
+        }
+        return Ok(());
+    }
+
+    if let Cmd::Search { args, all_vendors: true, .. } = &params.cmd {
+        let backends: Vec<Box<dyn VendorBackend>> = params
+            .backends()
+            .into_iter()
+            .filter(|backend| backend.is_available())
+            .collect();
+        if dry_run {
+            for backend in backends.iter() {
+                if let Some(command) = backend.command_line(PlsCommand::Search, &[args.to_owned()], false) {
+                    eprintln!("{}", command);
+                }
+            }
+            return Ok(());
+        }
+        let results = search_all(&backends, args);
+        if output.is_parsed() {
+            page(&output.render(&parse_results(&backends, &results))?, use_pager)?;
+        } else {
+            page(&merge_results(&results), use_pager)?;
+        }
+        return Ok(());
+    }
+
     let cmd: PlsCommand = (&params.cmd).into();
-    let vendor = match params.vendor {
-        Some(vendor) => vendor,
-        None => Vendor::new()?,
+
+    if let Cmd::Install { .. } = &params.cmd {
+        let files = PackageFile::files(&args)?;
+        let chain = params.chain()?;
+        if !files.is_empty() {
+            for (index, files) in plan_files(&chain, &files)? {
+                let vendor = &chain[index];
+                if dry_run {
+                    eprintln!("using {}: installs {}", vendor.name(), files.join(", "));
+                }
+                let su = params.elevation(vendor.as_ref(), PlsCommand::InstallFile)?;
+                let status = vendor.execute(PlsCommand::InstallFile, &files, assume_yes, su, dry_run, None)?;
+                if status != 0 {
+                    exit(status);
+                }
+            }
+            args.retain(|arg| PackageFile::detect(arg).is_none());
+            if args.is_empty() {
+                return Ok(());
+            }
+        }
+        if chain.len() > 1 {
+            for (index, packages) in plan_install(&chain, &args, &params.aliases)? {
+                let vendor = &chain[index];
+                if dry_run {
+                    eprintln!("using {}: provides {}", vendor.name(), packages.join(", "));
+                }
+                let packages = vendor.versioned(&packages)?;
+                let su = params.elevation(vendor.as_ref(), cmd)?;
+                let status = vendor.execute(cmd, &packages, assume_yes, su, dry_run, None)?;
+                if status != 0 {
+                    exit(status);
+                }
+            }
+            return Ok(());
+        }
+    }
+
+    let (vendor, reason) = params.detect()?;
+    if dry_run {
+        eprintln!("using {}: {}", vendor.name(), reason);
+    }
+    if let Cmd::Export { all_vendors } = params.cmd {
+        let mut set = PackageSet::default();
+        if all_vendors {
+            for backend in params.backends().iter().filter(|backend| backend.is_available() && backend.parser().is_some()) {
+                set.add(&backend.name(), &requested(backend.as_ref())?);
+            }
+        } else {
+            set.add(&vendor.name(), &requested(vendor.as_ref())?);
+        }
+        print!("{}", set.to_toml()?);
+        return Ok(());
+    }
+
+    if let Cmd::Import { file } = &params.cmd {
+        let set = PackageSet::load(Path::new(file))?;
+        let backends = params.backends();
+        for source in set.vendors() {
+            let target = backends
+                .iter()
+                .find(|backend| backend.name() == source && backend.is_available())
+                .unwrap_or(&vendor);
+            let packages = set.names(&source, &target.name(), &params.aliases);
+            if dry_run {
+                eprintln!("using {}: exported from {}", target.name(), source);
+            }
+            let su = params.elevation(target.as_ref(), PlsCommand::Install)?;
+            let status = target.execute(PlsCommand::Install, &packages, assume_yes, su, dry_run, None)?;
+            if status != 0 {
+                exit(status);
+            }
+        }
+        return Ok(());
+    }
+
+    if let Cmd::Apply { file, prune } = &params.cmd {
+        let manifest = Manifest::load(Path::new(file))?;
+        let installed = vendor.packages(PlsCommand::List, &[])?;
+        // pruning must not reach dependencies or the base system
+        let explicit = if vendor.supports(PlsCommand::Explicit) {
+            vendor.explicit_packages()?
+        } else if *prune {
+            return Err(eyre!("{} cannot tell which packages were installed on request, refusing to prune", vendor.name()));
+        } else {
+            Vec::new()
+        };
+        let plan = manifest.plan(&vendor.name(), &params.aliases, &installed, &explicit);
+        print_plan(&plan, *prune);
+        let mut unchecked = false;
+        let mut steps = vec![
+            (PlsCommand::Install, requirements(vendor.as_ref(), &plan.install, &mut unchecked)),
+            (PlsCommand::Upgrade, requirements(vendor.as_ref(), &plan.upgrade, &mut unchecked)),
+        ];
+        if *prune {
+            steps.push((PlsCommand::Remove, plan.extra.clone()));
+        }
+        for (cmd, packages) in steps {
+            if packages.is_empty() {
+                continue;
+            }
+            let su = params.elevation(vendor.as_ref(), cmd)?;
+            let status = vendor.execute(cmd, &packages, assume_yes, su, dry_run, None)?;
+            if status != 0 {
+                exit(status);
+            }
+        }
+        if unchecked && !dry_run {
+            let installed = vendor.packages(PlsCommand::List, &[])?;
+            let after = manifest.plan(&vendor.name(), &params.aliases, &installed, &explicit);
+            for unmet in after.install.iter().chain(after.upgrade.iter()).chain(after.conflicts.iter()) {
+                eprintln!("cannot satisfy: {}", unmet);
+            }
+            if !after.is_empty() || !after.conflicts.is_empty() {
+                exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    let mut args = match cmd {
+        PlsCommand::Install | PlsCommand::Remove | PlsCommand::Upgrade | PlsCommand::Info | PlsCommand::Files |
+        PlsCommand::Hold | PlsCommand::Unhold =>
+            params.aliases.translate_all(&vendor.name(), &args),
+        PlsCommand::UpgradeAll => vendor.exclusions(Holds::load()?.get(&vendor.name())),
+        _ => args,
     };
-    #[cfg(target_os = "windows")]
-    let su = false;
-    #[cfg(not(target_os = "windows"))]
-    let su = params.su;
+    if cmd == PlsCommand::Install {
+        args = vendor.versioned(&args)?;
+    }
+
+    if matches!(cmd, PlsCommand::Hold | PlsCommand::Unhold | PlsCommand::Holds)
+        && !vendor.supports(cmd)
+        && vendor.exclude().is_some()
+    {
+        return keep_holds(&vendor.name(), cmd, &args, dry_run);
+    }
 
+    if output.is_parsed() && matches!(cmd, PlsCommand::Search | PlsCommand::List | PlsCommand::Info) && !dry_run {
+        let packages: Vec<(String, Package)> = vendor
+            .packages(cmd, &args)?
+            .into_iter()
+            .map(|package| (vendor.name(), package))
+            .collect();
+        page(&output.render(&packages)?, use_pager)?;
+        return Ok(());
+    }
+
+    let su = params.elevation(vendor.as_ref(), cmd)?;
     let status = vendor.execute(cmd, &args, assume_yes, su, dry_run, use_pager)?;
     exit(status);
 }
+
+/// Hold packages in please’s own list, for vendors that cannot hold them
+fn keep_holds(vendor: &str, cmd: PlsCommand, args: &[String], dry_run: bool) -> Result<()> {
+    let mut holds = Holds::load()?;
+    match cmd {
+        PlsCommand::Hold => holds.hold(vendor, args),
+        PlsCommand::Unhold => holds.unhold(vendor, args),
+        _ => {
+            for package in holds.get(vendor) {
+                println!("{}", package);
+            }
+            return Ok(());
+        }
+    }
+    if dry_run {
+        eprintln!("{} {} in {}", cmd, args.join(" "), Holds::path().display());
+        return Ok(());
+    }
+    holds.save()
+}
+
+/// One aligned line per package for the picker, tagged with the vendor when
+/// several vendors were searched
+fn describe(found: &[(String, Package)], all_vendors: bool) -> Vec<String> {
+    let vendor_width = found.iter().map(|(vendor, _)| vendor.chars().count()).max().unwrap_or_default();
+    let name_width = found.iter().map(|(_, package)| package.name.chars().count()).max().unwrap_or_default();
+    let version_width = found
+        .iter()
+        .map(|(_, package)| package.version.as_deref().unwrap_or_default().chars().count())
+        .max()
+        .unwrap_or_default();
+    found
+        .iter()
+        .map(|(vendor, package)| {
+            let line = format!(
+                "{:<name_width$}  {:<version_width$}  {}",
+                package.name,
+                package.version.as_deref().unwrap_or_default(),
+                package.description.as_deref().unwrap_or_default(),
+            );
+            let line = if all_vendors {format!("{:<vendor_width$}  {}", vendor, line)} else {line};
+            line.trim_end().to_owned()
+        })
+        .collect()
+}
+
+/// The installed packages, only those installed on request when the vendor
+/// can tell them from dependencies
+fn requested(vendor: &dyn VendorBackend) -> Result<Vec<Package>> {
+    let mut packages = vendor.packages(PlsCommand::List, &[])?;
+    if vendor.supports(PlsCommand::Explicit) {
+        let explicit = vendor.explicit_packages()?;
+        packages.retain(|package| explicit.contains(&package.name));
+    }
+    Ok(packages)
+}
+
+/// The packages with their version requirement written for the vendor, or
+/// bare when it cannot express it, flagging them to be checked once installed
+fn requirements(vendor: &dyn VendorBackend, packages: &[String], unchecked: &mut bool) -> Vec<String> {
+    let mut arguments = Vec::new();
+    for package in packages.iter() {
+        match vendor.versioned(std::slice::from_ref(package)) {
+            Ok(versioned) => arguments.extend(versioned),
+            Err(err) => {
+                eprintln!("{}, taking the latest version and checking it afterwards", err);
+                arguments.push(split_version(package).0.to_owned());
+                *unchecked = true;
+            }
+        }
+    }
+    arguments
+}
+
+fn print_plan(plan: &Plan, prune: bool) {
+    if plan.is_empty() && (!prune || plan.extra.is_empty()) {
+        eprintln!("nothing to do");
+    }
+    if !plan.install.is_empty() {
+        eprintln!("install: {}", plan.install.join(" "));
+    }
+    if !plan.upgrade.is_empty() {
+        eprintln!("upgrade: {}", plan.upgrade.join(" "));
+    }
+    for conflict in plan.conflicts.iter() {
+        eprintln!("cannot satisfy: {}", conflict);
+    }
+    if prune && !plan.extra.is_empty() {
+        eprintln!("remove: {}", plan.extra.join(" "));
+    } else if !plan.extra.is_empty() {
+        eprintln!("{} installed packages not in the manifest (use --prune to remove them)", plan.extra.len());
+    }
+}
+
+fn print_history(transactions: &[&Transaction]) {
+    if transactions.is_empty() {
+        eprintln!("no history");
+        return;
+    }
+    let vendor_width = transactions.iter().map(|transaction| transaction.vendor.chars().count()).max().unwrap_or_default();
+    let command_width = transactions.iter().map(|transaction| transaction.command.chars().count()).max().unwrap_or_default();
+    for transaction in transactions.iter() {
+        let line = format!(
+            "{:>4}  {}  {:<vendor_width$}  {:<command_width$}  {:>3}  {:>6.1}s  {}",
+            transaction.id,
+            transaction.timestamp,
+            transaction.vendor,
+            transaction.command,
+            transaction.status,
+            transaction.duration,
+            transaction.packages.join(" "),
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+fn print_templates(vendor: &dyn VendorBackend) {
+    println!("  {:<12} {}", "bin", vendor.binary());
+    println!("  {:<12} {}", "yes", vendor.yes_flag());
+    let root: Vec<String> = PlsCommand::iter()
+        .filter(|command| vendor.root(*command) == Root::Needed)
+        .map(|command| command.to_string())
+        .collect();
+    let user = PlsCommand::iter().any(|command| vendor.root(command) == Root::Harmful);
+    let root = match (user, root.is_empty()) {
+        (true, true) => "never (per-user packages)".to_owned(),
+        (true, false) => format!("{}, never the others (per-user packages)", root.join(", ")),
+        (false, true) => "with --su".to_owned(),
+        (false, false) => root.join(", "),
+    };
+    println!("  {:<12} {}", "root", root);
+    println!("  {:<12} {}", "exclude", vendor.exclude().unwrap_or("(not supported)"));
+    let versions: Vec<String> = Op::REQUIREMENTS
+        .into_iter()
+        .filter_map(|op| vendor.version_format(op).map(|format| format!("{} {}", op.symbol(), format)))
+        .collect();
+    println!("  {:<12} {}", "versions", if versions.is_empty() {"(not supported)".to_owned()} else {versions.join(", ")});
+    for command in PlsCommand::iter() {
+        let template = vendor.template(command).unwrap_or("(not supported)");
+        println!("  {:<12} {}", command.to_string(), template);
+    }
+}
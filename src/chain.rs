@@ -0,0 +1,55 @@
+use eyre::{eyre, Result};
+use crate::{split_version, Aliases, PackageFile, PlsCommand, VendorBackend};
+
+
+/// Assign each package to the first vendor of the chain that provides it
+///
+/// Vendors that cannot tell whether they provide a package are assumed to
+/// provide it. Returns the packages, translated to each vendor’s names,
+/// grouped by vendor index, in chain order.
+pub fn plan_install(chain: &[Box<dyn VendorBackend>], packages: &[String], aliases: &Aliases) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut plan: Vec<(usize, Vec<String>)> = Vec::new();
+    for package in packages.iter() {
+        let index = chain
+            .iter()
+            .position(|vendor| {
+                let package = aliases.translate(&vendor.name(), package);
+                vendor.has_package(split_version(&package).0).unwrap_or(true)
+            })
+            .ok_or_else(|| eyre!(
+                "package {} not found in {}",
+                package,
+                chain.iter().map(|vendor| vendor.name()).collect::<Vec<String>>().join(", "),
+            ))?;
+        let package = aliases.translate(&chain[index].name(), package);
+        match plan.iter_mut().find(|(vendor, _)| *vendor == index) {
+            Some((_, group)) => group.push(package),
+            None => plan.push((index, vec![package])),
+        }
+    }
+    plan.sort_by_key(|(index, _)| *index);
+    Ok(plan)
+}
+
+/// Assign each package file to the first vendor of the chain that can install
+/// files of its kind and has an `install-file` template, grouped by vendor index, in chain order
+pub fn plan_files(chain: &[Box<dyn VendorBackend>], files: &[(String, PackageFile)]) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut plan: Vec<(usize, Vec<String>)> = Vec::new();
+    for (file, kind) in files.iter() {
+        let index = chain
+            .iter()
+            .position(|vendor| kind.accepts(&vendor.name()) && vendor.supports(PlsCommand::InstallFile))
+            .ok_or_else(|| eyre!(
+                "{}: {}s cannot be installed by {}",
+                file,
+                kind,
+                chain.iter().map(|vendor| vendor.name()).collect::<Vec<String>>().join(", "),
+            ))?;
+        match plan.iter_mut().find(|(vendor, _)| *vendor == index) {
+            Some((_, group)) => group.push(file.to_owned()),
+            None => plan.push((index, vec![file.to_owned()])),
+        }
+    }
+    plan.sort_by_key(|(index, _)| *index);
+    Ok(plan)
+}
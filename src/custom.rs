@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use eyre::{eyre, Result};
+use toml::Table;
+use strum::IntoEnumIterator;
+use crate::{Op, Parser, PlsCommand, Root, Vendor, VendorBackend};
+
+
+/// A vendor declared at runtime in a `[vendors.<name>]` settings table
+///
+/// When the name matches a built-in vendor, the table only overrides the
+/// templates it sets, and the rest keep their defaults.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomVendor {
+    pub name: String,
+    pub bin: String,
+    pub yes: String,
+    /// operations that must run as root
+    pub root: Vec<PlsCommand>,
+    /// manages per-user packages, never run as root but for `root`
+    pub user: bool,
+    pub exclude: Option<String>,
+    /// version formats keyed by requirement symbol (`=`, `@`, `>=`…)
+    pub versions: HashMap<String, String>,
+    pub parser: Option<Parser>,
+    pub templates: HashMap<PlsCommand, String>,
+}
+
+impl CustomVendor {
+    pub fn from_table(name: &str, table: &Table) -> Result<Self> {
+        let mut vendor = match Vendor::try_from(name) {
+            Ok(vendor) => vendor.into(),
+            Err(_) => Self {
+                name: name.to_owned(),
+                bin: name.to_owned(),
+                yes: String::new(),
+                root: Vec::new(),
+                user: false,
+                exclude: None,
+                versions: HashMap::new(),
+                parser: None,
+                templates: HashMap::new(),
+            },
+        };
+        for (key, value) in table.iter() {
+            if key == "root" {
+                let commands = value.as_array().ok_or_else(|| eyre!("vendor {}: root must be a list of commands", name))?;
+                vendor.root = commands
+                    .iter()
+                    .map(|command| {
+                        let command = command.as_str().ok_or_else(|| eyre!("vendor {}: root must be a list of commands", name))?;
+                        PlsCommand::try_from(command).map_err(|err| eyre!("vendor {}: {}", name, err))
+                    })
+                    .collect::<Result<Vec<PlsCommand>>>()?;
+                continue;
+            }
+            if key == "user" {
+                vendor.user = value.as_bool().ok_or_else(|| eyre!("vendor {}: user must be a boolean", name))?;
+                continue;
+            }
+            if key == "versions" {
+                let versions = value.as_table().ok_or_else(|| eyre!("vendor {}: versions must be a table", name))?;
+                for (symbol, format) in versions.iter() {
+                    let format = format
+                        .as_str()
+                        .ok_or_else(|| eyre!("vendor {}: version format {} must be a string", name, symbol))?;
+                    if format.is_empty() {
+                        vendor.versions.remove(symbol);
+                    } else {
+                        vendor.versions.insert(symbol.to_owned(), format.to_owned());
+                    }
+                }
+                continue;
+            }
+            let value = value
+                .as_str()
+                .ok_or_else(|| eyre!("vendor {}: {} must be a string", name, key))?
+                .to_owned();
+            match key.as_str() {
+                "bin" => vendor.bin = value,
+                "yes" => vendor.yes = value,
+                "exclude" => vendor.exclude = Some(value).filter(|value| !value.is_empty()),
+                "parser" => {
+                    vendor.parser = Some(Parser::try_from(value.as_str()).map_err(|err| eyre!("vendor {}: {}", name, err))?);
+                }
+                key => {
+                    let command = PlsCommand::try_from(key).map_err(|err| eyre!("vendor {}: {}", name, err))?;
+                    if value.is_empty() {
+                        vendor.templates.remove(&command);
+                    } else {
+                        vendor.templates.insert(command, value);
+                    }
+                }
+            }
+        }
+        Ok(vendor)
+    }
+
+    fn get(&self, command: PlsCommand) -> Option<&str> {
+        self.templates.get(&command).map(|template| template.as_str())
+    }
+}
+
+impl From<Vendor> for CustomVendor {
+    fn from(value: Vendor) -> Self {
+        let templates = PlsCommand::iter()
+            .filter_map(|command| value.template(command).map(|template| (command, template.to_owned())))
+            .collect();
+        Self {
+            name: value.name(),
+            bin: value.binary().to_owned(),
+            yes: value.yes_flag().to_owned(),
+            root: PlsCommand::iter().filter(|command| value.root(*command) == Root::Needed).collect(),
+            user: PlsCommand::iter().any(|command| value.root(command) == Root::Harmful),
+            exclude: value.exclude().map(|exclude| exclude.to_owned()),
+            versions: Op::REQUIREMENTS
+                .into_iter()
+                .filter_map(|op| value.version_format(op).map(|format| (op.symbol().to_owned(), format.to_owned())))
+                .collect(),
+            parser: value.parser(),
+            templates,
+        }
+    }
+}
+
+impl VendorBackend for CustomVendor {
+    fn name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    fn binary(&self) -> &str {
+        &self.bin
+    }
+
+    fn yes_flag(&self) -> &str {
+        &self.yes
+    }
+
+    fn root(&self, command: PlsCommand) -> Root {
+        if self.root.contains(&command) {
+            Root::Needed
+        } else if self.user {
+            Root::Harmful
+        } else {
+            Root::Optional
+        }
+    }
+
+    fn exclude(&self) -> Option<&str> {
+        self.exclude.as_deref()
+    }
+
+    fn version_format(&self, op: Op) -> Option<&str> {
+        self.versions.get(op.symbol()).map(|format| format.as_str())
+    }
+
+    fn install(&self) -> Option<&str> {
+        self.get(PlsCommand::Install)
+    }
+
+    fn remove(&self) -> Option<&str> {
+        self.get(PlsCommand::Remove)
+    }
+
+    fn upgrade(&self) -> Option<&str> {
+        self.get(PlsCommand::Upgrade)
+    }
+
+    fn search(&self) -> Option<&str> {
+        self.get(PlsCommand::Search)
+    }
+
+    fn info(&self) -> Option<&str> {
+        self.get(PlsCommand::Info)
+    }
+
+    fn update(&self) -> Option<&str> {
+        self.get(PlsCommand::Update)
+    }
+
+    fn upgrade_all(&self) -> Option<&str> {
+        self.get(PlsCommand::UpgradeAll)
+    }
+
+    fn list(&self) -> Option<&str> {
+        self.get(PlsCommand::List)
+    }
+
+    fn outdated(&self) -> Option<&str> {
+        self.get(PlsCommand::Outdated)
+    }
+
+    fn autoremove(&self) -> Option<&str> {
+        self.get(PlsCommand::Autoremove)
+    }
+
+    fn clean(&self) -> Option<&str> {
+        self.get(PlsCommand::Clean)
+    }
+
+    fn owns(&self) -> Option<&str> {
+        self.get(PlsCommand::Owns)
+    }
+
+    fn provides(&self) -> Option<&str> {
+        self.get(PlsCommand::Provides)
+    }
+
+    fn files(&self) -> Option<&str> {
+        self.get(PlsCommand::Files)
+    }
+
+    fn hold(&self) -> Option<&str> {
+        self.get(PlsCommand::Hold)
+    }
+
+    fn unhold(&self) -> Option<&str> {
+        self.get(PlsCommand::Unhold)
+    }
+
+    fn holds(&self) -> Option<&str> {
+        self.get(PlsCommand::Holds)
+    }
+
+    fn repo_list(&self) -> Option<&str> {
+        self.get(PlsCommand::RepoList)
+    }
+
+    fn repo_add(&self) -> Option<&str> {
+        self.get(PlsCommand::RepoAdd)
+    }
+
+    fn repo_remove(&self) -> Option<&str> {
+        self.get(PlsCommand::RepoRemove)
+    }
+
+    fn repo_enable(&self) -> Option<&str> {
+        self.get(PlsCommand::RepoEnable)
+    }
+
+    fn repo_disable(&self) -> Option<&str> {
+        self.get(PlsCommand::RepoDisable)
+    }
+
+    fn install_file(&self) -> Option<&str> {
+        self.get(PlsCommand::InstallFile)
+    }
+
+    fn rollback(&self) -> Option<&str> {
+        self.get(PlsCommand::Rollback)
+    }
+
+    fn available(&self) -> Option<&str> {
+        self.get(PlsCommand::Available)
+    }
+
+    fn explicit(&self) -> Option<&str> {
+        self.get(PlsCommand::Explicit)
+    }
+
+    fn parser(&self) -> Option<Parser> {
+        self.parser
+    }
+}
@@ -0,0 +1,130 @@
+use std::{fmt::Display, fs};
+#[allow(unused_imports)]
+use crate::Vendor::{self, *};
+
+
+/// Distribution identification read from `/etc/os-release`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OsRelease {
+    pub id: String,
+    pub id_like: Vec<String>,
+}
+
+/// Why a vendor was chosen
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reason {
+    /// set by `--vendor` or the settings
+    Requested,
+    /// first available in the settings priority list
+    Priority,
+    /// preferred for the distribution with the given ID
+    Distro(String),
+    /// no preference applied, first available vendor
+    FirstAvailable,
+}
+
+impl OsRelease {
+    pub fn load() -> Option<Self> {
+        ["/etc/os-release", "/usr/lib/os-release"]
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .map(|content| Self::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let mut release = Self::default();
+        for line in content.lines() {
+            let Some((key, value)) = line.trim().split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            match key.trim() {
+                "ID" => release.id = value.to_lowercase(),
+                "ID_LIKE" => release.id_like = value.split_whitespace().map(|id| id.to_lowercase()).collect(),
+                _ => (),
+            }
+        }
+        release
+    }
+
+    /// The distribution ID followed by the ones it resembles
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.id.as_str())
+            .chain(self.id_like.iter().map(|id| id.as_str()))
+            .filter(|id| !id.is_empty())
+    }
+
+    /// Preferred vendors for the distribution, most preferred first, along
+    /// with the ID that matched
+    pub fn preferred(&self) -> Option<(&str, &'static [Vendor])> {
+        self.ids().find_map(|id| {
+            DISTROS
+                .iter()
+                .find(|(ids, _)| ids.contains(&id))
+                .map(|(_, vendors)| (id, *vendors))
+        })
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Requested => write!(f, "requested by --vendor or settings"),
+            Reason::Priority => write!(f, "first available in the vendor priority list"),
+            Reason::Distro(id) => write!(f, "preferred for {} in os-release", id),
+            Reason::FirstAvailable => write!(f, "first available vendor"),
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+
+type Distro = (&'static [&'static str], &'static [Vendor]);
+
+static DISTROS: &[Distro] = &[
+    #[cfg(target_os = "linux")]
+    (
+        &[
+            "ubuntu", "debian", "linuxmint", "pop", "deepin", "elementary", "kali", "raspbian",
+            "aosc", "zorin", "antix", "devuan", "bodhi", "lxle", "sparky",
+        ],
+        &[Apt, Snap, Flatpak],
+    ),
+    #[cfg(target_os = "linux")]
+    (
+        &[
+            "fedora", "redhat", "rhel", "amzn", "ol", "almalinux", "rocky", "oubes", "centos",
+            "qubes", "eurolinux",
+        ],
+        &[Dnf, Yum, Flatpak],
+    ),
+    #[cfg(target_os = "linux")]
+    (
+        &["arch", "manjaro", "endeavouros", "arcolinux", "garuda", "antergos", "kaos"],
+        &[Pacman, Yay, Flatpak],
+    ),
+    #[cfg(target_os = "linux")]
+    (&["alpine", "postmarket", "postmarketos"], &[Apk]),
+    #[cfg(target_os = "linux")]
+    (&["opensuse", "opensuse-leap", "opensuse-tumbleweed", "suse"], &[Zypper, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["nixos"], &[NixEnv, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["guix"], &[Guix, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["gentoo", "funtoo"], &[Emerge, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["void"], &[Xbps, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["mageia"], &[Urpm, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["slackware"], &[Slackpkg, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["solus"], &[Eopkg, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["openwrt"], &[Opkg, Flatpak]),
+    #[cfg(target_os = "linux")]
+    (&["nutyx"], &[Cards, Flatpak]),
+    #[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "dragonfly", target_os = "netbsd"))]
+    (&["freebsd", "ghostbsd"], &[Pkg]),
+];
@@ -0,0 +1,71 @@
+use std::{fmt::Display, process::Command};
+use clap::ValueEnum;
+use strum::{EnumIter, IntoEnumIterator};
+
+
+/// Program used to run vendor commands as root
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, ValueEnum)]
+pub enum Elevator {
+    Sudo,
+    Doas,
+    Run0,
+    Pkexec,
+}
+
+impl Elevator {
+    /// The first elevator found in `PATH`, in declaration order
+    pub fn detect() -> Option<Self> {
+        Elevator::iter().find(|elevator| elevator.is_available())
+    }
+
+    pub fn is_available(&self) -> bool {
+        which::which(self.binary()).is_ok()
+    }
+
+    pub fn binary(&self) -> &'static str {
+        match self {
+            Elevator::Sudo => "sudo",
+            Elevator::Doas => "doas",
+            Elevator::Run0 => "run0",
+            Elevator::Pkexec => "pkexec",
+        }
+    }
+
+    /// Command running `program` through the elevator
+    pub fn command(&self, program: &str) -> Command {
+        let mut command = Command::new(self.binary());
+        command.arg(program);
+        command
+    }
+}
+
+/// Whether the process already runs as root, checked once
+pub fn is_root() -> bool {
+    #[cfg(target_os = "windows")]
+    return false;
+    #[cfg(not(target_os = "windows"))]
+    {
+        static ROOT: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+        *ROOT.get_or_init(|| {
+            Command::new("id")
+                .arg("-u")
+                .output()
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "0")
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Display for Elevator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.binary())
+    }
+}
+
+impl TryFrom<&str> for Elevator {
+    type Error = String;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        Elevator::from_str(value, true)
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fs, path::Path};
+use eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use crate::{Aliases, Package};
+
+
+/// Installed packages captured by `please export`, leaving out dependencies
+/// when the vendor can tell them apart
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackageSet {
+    #[serde(default, rename = "package")]
+    pub packages: Vec<Exported>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exported {
+    pub vendor: String,
+    pub name: String,
+    /// informational, import installs the latest version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl PackageSet {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|err| eyre!("{}: {}", path.display(), err))?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub fn add(&mut self, vendor: &str, packages: &[Package]) {
+        self.packages.extend(packages.iter().map(|package| Exported {
+            vendor: vendor.to_owned(),
+            name: package.name.to_owned(),
+            version: package.version.clone(),
+        }));
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Vendors the packages were exported from, in order of appearance
+    pub fn vendors(&self) -> Vec<String> {
+        let mut vendors: Vec<String> = Vec::new();
+        for package in self.packages.iter() {
+            if !vendors.contains(&package.vendor) {
+                vendors.push(package.vendor.to_owned());
+            }
+        }
+        vendors
+    }
+
+    /// Names of the packages exported from `source`, translated to `target`
+    /// through their canonical names when the vendors differ
+    pub fn names(&self, source: &str, target: &str, aliases: &Aliases) -> Vec<String> {
+        self.packages
+            .iter()
+            .filter(|package| package.vendor == source)
+            .map(|package| {
+                if source.eq_ignore_ascii_case(target) {
+                    package.name.to_owned()
+                } else {
+                    aliases.translate(target, &aliases.canonical(source, &package.name))
+                }
+            })
+            .collect()
+    }
+}
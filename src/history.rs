@@ -0,0 +1,188 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use crate::{split_version, state_dir, PlsCommand, VendorBackend};
+
+
+/// A vendor command that changed the system, as recorded in the history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transaction {
+    pub id: usize,
+    /// UTC, as `YYYY-MM-DDTHH:MM:SSZ`
+    pub timestamp: String,
+    pub vendor: String,
+    pub command: String,
+    pub packages: Vec<String>,
+    pub status: i32,
+    /// seconds
+    pub duration: f64,
+}
+
+impl Transaction {
+    /// The operation reverting this one with the vendor that made it: removing
+    /// what was installed, reinstalling what was removed, or the vendor’s own
+    /// rollback for everything else, which only reverts the vendor’s latest
+    /// change, so it is refused when the history has a later one
+    pub fn inverse(&self, vendor: &dyn VendorBackend, history: &History) -> Result<(PlsCommand, Vec<String>)> {
+        let command = PlsCommand::try_from(self.command.as_str()).map_err(|err| eyre!(err))?;
+        if self.status != 0 {
+            return Err(eyre!("#{} failed with status {}, nothing to undo", self.id, self.status));
+        }
+        match command {
+            PlsCommand::Install if !self.packages.is_empty() => {
+                let names = self.packages.iter().map(|package| split_version(package).0.to_owned()).collect();
+                Ok((PlsCommand::Remove, names))
+            }
+            PlsCommand::Remove if !self.packages.is_empty() => Ok((PlsCommand::Install, self.packages.clone())),
+            PlsCommand::Update => Err(eyre!("#{} only refreshed the package index, nothing to undo", self.id)),
+            PlsCommand::Rollback => Err(eyre!("#{} is a rollback, it cannot be undone", self.id)),
+            _ => match (vendor.rollback(), history.later(self)) {
+                (Some(_), Some(later)) => {
+                    Err(eyre!("{} can only roll back its latest change, which is #{}, not #{}", vendor.name(), later.id, self.id))
+                }
+                (Some(template), None) if template.contains("$args") && self.packages.is_empty() => {
+                    Err(eyre!("{} needs the packages to roll back, #{} did not name any", vendor.name(), self.id))
+                }
+                (Some(_), None) => Ok((PlsCommand::Rollback, self.packages.clone())),
+                (None, _) => Err(eyre!("{} cannot roll back {}", vendor.name(), command)),
+            },
+        }
+    }
+}
+
+/// Transactions kept one JSON object per line in `history.jsonl` under the
+/// state directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History(pub Vec<Transaction>);
+
+/// Operations worth recording
+const RECORDED: &[PlsCommand] = &[
+    PlsCommand::Install,
+    PlsCommand::InstallFile,
+    PlsCommand::Remove,
+    PlsCommand::Upgrade,
+    PlsCommand::UpgradeAll,
+    PlsCommand::Update,
+    PlsCommand::Autoremove,
+    PlsCommand::Rollback,
+];
+
+impl History {
+    pub fn path() -> PathBuf {
+        state_dir().join("history.jsonl")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|err| eyre!("{}: {}", path.display(), err))?;
+        let transactions = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|err| eyre!("{}: {}", path.display(), err)))
+            .collect::<Result<Vec<Transaction>>>()?;
+        Ok(Self(transactions))
+    }
+
+    pub fn records(command: PlsCommand) -> bool {
+        RECORDED.contains(&command)
+    }
+
+    /// Append a transaction, numbered after the last one
+    pub fn record(vendor: &str, command: PlsCommand, packages: &[String], status: i32, duration: Duration) -> Result<Transaction> {
+        let id = Self::load()?.0.last().map(|last| last.id).unwrap_or_default() + 1;
+        let transaction = Transaction {
+            id,
+            timestamp: timestamp(SystemTime::now()),
+            vendor: vendor.to_owned(),
+            command: command.to_string(),
+            packages: packages.to_vec(),
+            status,
+            duration: (duration.as_secs_f64() * 10.0).round() / 10.0,
+        };
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", serde_json::to_string(&transaction)?)?;
+        Ok(transaction)
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Transaction> {
+        self.0.iter().find(|transaction| transaction.id == id)
+    }
+
+    pub fn last(&self) -> Option<&Transaction> {
+        self.0.last()
+    }
+
+    /// The latest transaction made with the same vendor after this one
+    pub fn later(&self, transaction: &Transaction) -> Option<&Transaction> {
+        self.0.iter().rev().find(|later| later.id > transaction.id && later.vendor == transaction.vendor)
+    }
+
+    /// Transactions touching the package, between the dates, inclusive;
+    /// dates are `YYYY-MM-DD`, or a prefix of it such as `YYYY-MM`
+    pub fn filter(&self, package: Option<&str>, since: Option<&str>, until: Option<&str>) -> Result<Vec<&Transaction>> {
+        for date in since.iter().chain(until.iter()) {
+            if date.is_empty() || !date.chars().all(|c| c.is_ascii_digit() || c == '-') {
+                return Err(eyre!("invalid date {}, expected YYYY-MM-DD", date));
+            }
+        }
+        Ok(self.0
+            .iter()
+            .filter(|transaction| package.is_none_or(|package| transaction.packages.iter().any(|arg| names(arg, package))))
+            .filter(|transaction| since.is_none_or(|since| transaction.timestamp.as_str() >= since))
+            .filter(|transaction| until.is_none_or(|until| until_date(&transaction.timestamp, until)))
+            .collect())
+    }
+}
+
+/// Whether a recorded argument is the package, maybe with the version
+/// requirement the vendor got, like `nodejs=18.19*`, `nodejs-18.19*`, or
+/// `>=nodejs-18`
+fn names(arg: &str, package: &str) -> bool {
+    let arg = arg.trim_start_matches(['<', '>', '=', '~']);
+    split_version(arg).0 == package
+        || arg
+            .strip_prefix(package)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn until_date(timestamp: &str, until: &str) -> bool {
+    timestamp.get(..until.len()).unwrap_or(timestamp) <= until
+}
+
+/// Format a time as UTC `YYYY-MM-DDTHH:MM:SSZ`
+fn timestamp(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    // civil date from days since the epoch, after Howard Hinnant’s algorithm
+    let days = days as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 {month + 3} else {month - 9};
+    let year = year_of_era + era * 400 + if month <= 2 {1} else {0};
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    )
+}
@@ -0,0 +1,77 @@
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+use eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+
+
+/// Packages held by please itself, for vendors without a native hold
+///
+/// Kept as `vendor = ["package", ...]` in `holds.toml` under the state
+/// directory, and passed to `upgrade-all` through the vendor’s exclude flag.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Holds(BTreeMap<String, Vec<String>>);
+
+impl Holds {
+    pub fn path() -> PathBuf {
+        state_dir().join("holds.toml")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|err| eyre!("{}: {}", path.display(), err))?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string(self)?).map_err(|err| eyre!("{}: {}", path.display(), err))
+    }
+
+    pub fn get(&self, vendor: &str) -> &[String] {
+        self.0.get(&vendor.to_lowercase()).map(|packages| packages.as_slice()).unwrap_or_default()
+    }
+
+    pub fn hold(&mut self, vendor: &str, packages: &[String]) {
+        let held = self.0.entry(vendor.to_lowercase()).or_default();
+        for package in packages.iter() {
+            if !held.contains(package) {
+                held.push(package.to_owned());
+            }
+        }
+    }
+
+    pub fn unhold(&mut self, vendor: &str, packages: &[String]) {
+        let vendor = vendor.to_lowercase();
+        if let Some(held) = self.0.get_mut(&vendor) {
+            held.retain(|package| !packages.contains(package));
+            if held.is_empty() {
+                self.0.remove(&vendor);
+            }
+        }
+    }
+}
+
+/// Where please keeps its own state: `$XDG_STATE_HOME/please`, or
+/// `~/.local/state/please` (`%LOCALAPPDATA%\please` on Windows)
+pub fn state_dir() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    const XDG_STATE_HOME: &str = "LOCALAPPDATA";
+    #[cfg(not(target_os = "windows"))]
+    const XDG_STATE_HOME: &str = "XDG_STATE_HOME";
+
+    #[cfg(target_os = "windows")]
+    const STATE_HOME: &str = "AppData/Local";
+    #[cfg(not(target_os = "windows"))]
+    const STATE_HOME: &str = ".local/state";
+
+    let state_home = match env::var(XDG_STATE_HOME) {
+        Ok(state_home) if !state_home.is_empty() => PathBuf::from(state_home),
+        _ => PathBuf::from(env::var("HOME").unwrap_or_default()).join(STATE_HOME),
+    };
+    state_home.join("please")
+}
@@ -1,5 +1,35 @@
+mod aliases;
+mod chain;
+mod custom;
+mod detect;
+mod elevator;
+mod export;
+mod history;
+mod holds;
+mod local;
+mod manifest;
+mod output;
+mod package;
 mod params;
+mod picker;
+mod search;
 mod vendors;
+mod version;
 
+pub use aliases::*;
+pub use chain::*;
+pub use custom::*;
+pub use detect::*;
+pub use elevator::*;
+pub use export::*;
+pub use history::*;
+pub use holds::*;
+pub use local::*;
+pub use manifest::*;
+pub use output::*;
+pub use package::*;
 pub use params::*;
+pub use picker::*;
+pub use search::*;
 pub use vendors::*;
+pub use version::*;
@@ -0,0 +1,87 @@
+use std::{fmt::Display, fs, path::Path};
+use eyre::{eyre, Result};
+use strum::{EnumIter, IntoEnumIterator};
+
+
+/// Kind of a package file or URL given to `please install`, told by its
+/// extension
+#[derive(Debug, Clone, Copy, EnumIter, PartialEq)]
+pub enum PackageFile {
+    Deb,
+    Rpm,
+    Pacman,
+    Apk,
+    Flatpakref,
+    Snap,
+}
+
+impl PackageFile {
+    pub fn detect(arg: &str) -> Option<Self> {
+        let name = arg.to_lowercase();
+        let name = name.split(['?', '#']).next().unwrap_or_default();
+        PackageFile::iter().find(|kind| kind.extensions().iter().any(|extension| name.ends_with(extension)))
+    }
+
+    /// The install arguments that are package files, ready to be passed to
+    /// the vendor, along with their kind
+    pub fn files(args: &[String]) -> Result<Vec<(String, Self)>> {
+        args.iter()
+            .filter_map(|arg| Self::detect(arg).map(|kind| Self::path(arg).map(|path| (path, kind))))
+            .collect()
+    }
+
+    /// Whether the vendor can install files of this kind
+    pub fn accepts(&self, vendor: &str) -> bool {
+        let vendor = vendor.to_lowercase();
+        self.vendors().contains(&vendor.as_str())
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            PackageFile::Deb => &[".deb"],
+            PackageFile::Rpm => &[".rpm"],
+            PackageFile::Pacman => &[".pkg.tar.zst", ".pkg.tar.xz", ".pkg.tar.gz", ".pkg.tar"],
+            PackageFile::Apk => &[".apk"],
+            PackageFile::Flatpakref => &[".flatpakref"],
+            PackageFile::Snap => &[".snap"],
+        }
+    }
+
+    fn vendors(&self) -> &'static [&'static str] {
+        match self {
+            PackageFile::Deb => &["apt", "termux"],
+            PackageFile::Rpm => &["dnf", "yum", "zypper", "urpm"],
+            PackageFile::Pacman => &["pacman", "yay"],
+            PackageFile::Apk => &["apk"],
+            PackageFile::Flatpakref => &["flatpak"],
+            PackageFile::Snap => &["snap"],
+        }
+    }
+
+    /// URLs are kept as they are; local files must exist, and are made
+    /// absolute, since elevators like pkexec change the working directory
+    fn path(arg: &str) -> Result<String> {
+        if ["http://", "https://", "ftp://", "file://"].iter().any(|scheme| arg.starts_with(scheme)) {
+            return Ok(arg.to_owned());
+        }
+        let path = Path::new(arg);
+        if !path.is_file() {
+            return Err(eyre!("{}: no such file", arg));
+        }
+        let path = fs::canonicalize(path).map_err(|err| eyre!("{}: {}", arg, err))?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+}
+
+impl Display for PackageFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackageFile::Deb => write!(f, "Debian package"),
+            PackageFile::Rpm => write!(f, "RPM package"),
+            PackageFile::Pacman => write!(f, "pacman package"),
+            PackageFile::Apk => write!(f, "Alpine package"),
+            PackageFile::Flatpakref => write!(f, "Flatpak reference"),
+            PackageFile::Snap => write!(f, "snap package"),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::{cmp::Ordering, collections::HashMap, fs, path::Path};
+use eyre::{eyre, Result};
+use toml::{Table, Value};
+use crate::{compare, Aliases, Constraint, Package};
+
+
+/// Packages a machine should have, read from a `Pleasefile.toml`
+///
+/// ```toml
+/// [packages]
+/// git = "*"
+/// nodejs = ">=18"
+/// fd = { apt = "fd-find" }
+/// htop = { version = ">=3", winget = false }
+/// ```
+///
+/// A plain `packages = ["git", "vim"]` list is also accepted. Per-vendor
+/// entries name the package for that vendor, or skip it when `false`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Manifest {
+    pub packages: Vec<Wanted>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wanted {
+    pub name: String,
+    pub constraint: Option<Constraint>,
+    /// vendor-specific names, `None` when the vendor should skip it
+    pub vendors: HashMap<String, Option<String>>,
+}
+
+/// What must change to match a manifest, in vendor-specific names
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Plan {
+    /// missing packages, with their manifest version requirement, like
+    /// `nodejs>=18`
+    pub install: Vec<String>,
+    /// installed packages older than the manifest wants, with their version
+    /// requirement
+    pub upgrade: Vec<String>,
+    /// installed packages newer than the manifest allows, which upgrading
+    /// cannot fix
+    pub conflicts: Vec<String>,
+    /// packages installed on request that are not in the manifest
+    pub extra: Vec<String>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|err| eyre!("{}: {}", path.display(), err))?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let table: Table = content.parse()?;
+        let mut manifest = Self::default();
+        match table.get("packages") {
+            Some(Value::Array(names)) => {
+                for name in names.iter() {
+                    let name = name.as_str().ok_or_else(|| eyre!("package names must be strings"))?;
+                    manifest.packages.push(Wanted::new(name));
+                }
+            }
+            Some(Value::Table(packages)) => {
+                for (name, spec) in packages.iter() {
+                    manifest.packages.push(Wanted::from_spec(name, spec)?);
+                }
+            }
+            Some(_) => return Err(eyre!("packages must be a list or a table")),
+            None => (),
+        }
+        Ok(manifest)
+    }
+
+    /// Compare the manifest against the packages installed by the vendor;
+    /// `explicit` lists those installed on request, extra packages are only
+    /// looked for among them
+    pub fn plan(&self, vendor: &str, aliases: &Aliases, installed: &[Package], explicit: &[String]) -> Plan {
+        let mut plan = Plan::default();
+        let mut wanted: Vec<String> = Vec::new();
+        for package in self.packages.iter() {
+            let Some(name) = package.name_for(vendor, aliases) else {
+                continue;
+            };
+            wanted.push(name.to_owned());
+            let requirement = match &package.constraint {
+                Some(constraint) => constraint.requirement(&name),
+                None => name.to_owned(),
+            };
+            match installed.iter().find(|installed| installed.name == name) {
+                None => plan.install.push(requirement),
+                Some(installed) => {
+                    let (Some(constraint), Some(version)) = (&package.constraint, &installed.version) else {
+                        continue;
+                    };
+                    if constraint.matches(version) {
+                        continue;
+                    }
+                    if compare(version, &constraint.version) == Ordering::Less {
+                        plan.upgrade.push(requirement);
+                    } else {
+                        plan.conflicts.push(format!("{} {} (wants {})", name, version, constraint));
+                    }
+                }
+            }
+        }
+        plan.extra = installed
+            .iter()
+            .filter(|package| !wanted.contains(&package.name) && explicit.contains(&package.name))
+            .map(|package| package.name.to_owned())
+            .collect();
+        plan
+    }
+}
+
+impl Wanted {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            constraint: None,
+            vendors: HashMap::new(),
+        }
+    }
+
+    fn from_spec(name: &str, spec: &Value) -> Result<Self> {
+        let mut wanted = Self::new(name);
+        match spec {
+            Value::String(constraint) => wanted.constraint = Some(Constraint::parse(constraint).map_err(|err| eyre!(err))?),
+            Value::Table(spec) => {
+                for (key, value) in spec.iter() {
+                    match (key.as_str(), value) {
+                        ("version", Value::String(constraint)) => {
+                            wanted.constraint = Some(Constraint::parse(constraint).map_err(|err| eyre!(err))?);
+                        }
+                        (vendor, Value::String(name)) => {
+                            wanted.vendors.insert(vendor.to_lowercase(), Some(name.to_owned()));
+                        }
+                        (vendor, Value::Boolean(false)) => {
+                            wanted.vendors.insert(vendor.to_lowercase(), None);
+                        }
+                        (key, _) => return Err(eyre!("package {}: invalid value for {}", name, key)),
+                    }
+                }
+            }
+            _ => return Err(eyre!("package {}: expected a version or a table", name)),
+        }
+        Ok(wanted)
+    }
+
+    /// The package name for the vendor, or `None` if the vendor skips it
+    pub fn name_for(&self, vendor: &str, aliases: &Aliases) -> Option<String> {
+        match self.vendors.get(&vendor.to_lowercase()) {
+            Some(name) => name.clone(),
+            None => Some(aliases.translate(vendor, &self.name)),
+        }
+    }
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.install.is_empty() && self.upgrade.is_empty()
+    }
+}
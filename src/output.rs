@@ -0,0 +1,95 @@
+use clap::ValueEnum;
+use eyre::Result;
+use serde::Serialize;
+use crate::Package;
+
+
+/// How list, search, and info results are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum Output {
+    /// the vendor’s own output
+    #[default]
+    Plain,
+    /// parsed packages, aligned in columns
+    Table,
+    /// parsed packages as a JSON array
+    Json,
+    /// parsed packages as one JSON object per line
+    Ndjson,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    vendor: &'a str,
+    #[serde(flatten)]
+    package: &'a Package,
+}
+
+impl Output {
+    pub fn is_parsed(&self) -> bool {
+        *self != Output::Plain
+    }
+
+    /// Render packages tagged with the vendor that reported them
+    pub fn render(self, packages: &[(String, Package)]) -> Result<String> {
+        let records: Vec<Record> = packages
+            .iter()
+            .map(|(vendor, package)| Record { vendor, package })
+            .collect();
+        let text = match self {
+            Output::Json => format!("{}\n", serde_json::to_string_pretty(&records)?),
+            Output::Ndjson => {
+                let mut text = String::new();
+                for record in records.iter() {
+                    text.push_str(&serde_json::to_string(record)?);
+                    text.push('\n');
+                }
+                text
+            }
+            Output::Table | Output::Plain => table(&records),
+        };
+        Ok(text)
+    }
+}
+
+impl TryFrom<&str> for Output {
+    type Error = String;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        Output::from_str(value, true)
+    }
+}
+
+fn table(records: &[Record]) -> String {
+    let rows: Vec<[String; 6]> = records
+        .iter()
+        .map(|record| [
+            record.vendor.to_owned(),
+            record.package.name.to_owned(),
+            record.package.version.clone().unwrap_or_default(),
+            record.package.repository.clone().unwrap_or_default(),
+            if record.package.installed {"installed"} else {""}.to_owned(),
+            record.package.description.clone().unwrap_or_default(),
+        ])
+        .collect();
+    let mut widths = [0; 6];
+    for row in rows.iter() {
+        for (width, column) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(column.chars().count());
+        }
+    }
+    let mut text = String::new();
+    for row in rows.iter() {
+        let mut line = String::new();
+        for (index, column) in row.iter().enumerate() {
+            if index == row.len() - 1 {
+                line.push_str(column);
+            } else {
+                line.push_str(&format!("{:<width$}  ", column, width = widths[index]));
+            }
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,654 @@
+use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+use crate::PlsCommand;
+
+
+/// A package as reported by a vendor
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Package {
+    pub name: String,
+    pub version: Option<String>,
+    pub repository: Option<String>,
+    pub description: Option<String>,
+    pub installed: bool,
+}
+
+/// Output formats please knows how to read, shared by vendors built on the
+/// same tools (e.g. yay prints like pacman)
+#[derive(Debug, Clone, Copy, EnumIter, PartialEq)]
+pub enum Parser {
+    Apk,
+    Apt,
+    Dnf,
+    Flatpak,
+    NixEnv,
+    Pacman,
+    Snap,
+    Xbps,
+    Zypper,
+}
+
+impl Package {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Parser {
+    /// Parse the output of a search, list, or info command; `None` for any
+    /// other command
+    pub fn parse(self, command: PlsCommand, output: &str) -> Option<Vec<Package>> {
+        let packages = match (self, command) {
+            (Parser::Apk, PlsCommand::Search) => apk_search(output),
+            (Parser::Apk, PlsCommand::List) => apk_list(output),
+            (Parser::Apk, PlsCommand::Info) => apk_info(output),
+            (Parser::Apt, PlsCommand::Search) => apt_list(output),
+            (Parser::Apt, PlsCommand::List) => apt_list(output),
+            (Parser::Apt, PlsCommand::Info) => apt_info(output),
+            (Parser::Dnf, PlsCommand::Search) => dnf_search(output),
+            (Parser::Dnf, PlsCommand::List) => dnf_list(output),
+            (Parser::Dnf, PlsCommand::Info) => dnf_info(output),
+            (Parser::Flatpak, PlsCommand::Search) => flatpak_search(output),
+            (Parser::Flatpak, PlsCommand::List) => flatpak_list(output),
+            (Parser::Flatpak, PlsCommand::Info) => flatpak_info(output),
+            (Parser::NixEnv, PlsCommand::Search) => nix_search(output),
+            (Parser::NixEnv, PlsCommand::List) => nix_list(output),
+            (Parser::NixEnv, PlsCommand::Info) => nix_info(output),
+            (Parser::Pacman, PlsCommand::Search) => pacman_search(output),
+            (Parser::Pacman, PlsCommand::List) => pacman_list(output),
+            (Parser::Pacman, PlsCommand::Info) => pacman_info(output),
+            (Parser::Snap, PlsCommand::Search) => snap_search(output),
+            (Parser::Snap, PlsCommand::List) => snap_list(output),
+            (Parser::Snap, PlsCommand::Info) => snap_info(output),
+            (Parser::Xbps, PlsCommand::Search) => xbps_search(output),
+            (Parser::Xbps, PlsCommand::List) => xbps_list(output),
+            (Parser::Xbps, PlsCommand::Info) => xbps_info(output),
+            (Parser::Zypper, PlsCommand::Search) => zypper_table(output),
+            (Parser::Zypper, PlsCommand::List) => zypper_table(output),
+            (Parser::Zypper, PlsCommand::Info) => zypper_info(output),
+            _ => return None,
+        };
+        Some(packages)
+    }
+}
+
+impl Display for Parser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl TryFrom<&str> for Parser {
+    type Error = String;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let value = value.to_lowercase();
+        for parser in Parser::iter() {
+            if parser.to_string().to_lowercase() == value {
+                return Ok(parser);
+            }
+        }
+        Err(format!("invalid parser name {}", value))
+    }
+}
+
+//----------------------------------------------------------------------------//
+
+fn some(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_owned())
+    }
+}
+
+/// `Key: value` lines, ignoring continuation lines without a key
+fn fields(block: &str) -> Vec<(String, String)> {
+    block
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+fn field<'a>(fields: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    fields.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, value)| value.as_str())
+}
+
+/// Blocks of text separated by blank lines
+fn blocks(output: &str) -> Vec<String> {
+    let mut blocks = vec![String::new()];
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            blocks.push(String::new());
+        } else if let Some(block) = blocks.last_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+    blocks.into_iter().filter(|block| !block.is_empty()).collect()
+}
+
+/// Split `name-version` at the hyphen before the `skip`-th from last
+fn split_at_hyphen(pkgver: &str, skip: usize) -> (String, Option<String>) {
+    let hyphens: Vec<usize> = pkgver.match_indices('-').map(|(index, _)| index).collect();
+    if hyphens.len() <= skip {
+        return (pkgver.to_owned(), None);
+    }
+    let index = hyphens[hyphens.len() - 1 - skip];
+    (pkgver[..index].to_owned(), some(&pkgver[index + 1..]))
+}
+
+/// Split `name-version` at the first hyphen followed by a digit
+fn split_at_version(pkgver: &str) -> (String, Option<String>) {
+    let index = pkgver
+        .match_indices('-')
+        .map(|(index, _)| index)
+        .find(|index| pkgver[index + 1..].starts_with(|c: char| c.is_ascii_digit()));
+    match index {
+        Some(index) => (pkgver[..index].to_owned(), some(&pkgver[index + 1..])),
+        None => (pkgver.to_owned(), None),
+    }
+}
+
+//----------------------------------------------------------------------------//
+
+// vim-9.0.2127-r0
+fn apk_search(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|pkgver| {
+            let (name, version) = split_at_hyphen(pkgver, 1);
+            Package { version, ..Package::new(&name) }
+        })
+        .collect()
+}
+
+// vim-9.0.2127-r0 x86_64 {vim} (Vim) [installed]
+fn apk_list(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let pkgver = line.split_whitespace().next()?;
+            let (name, version) = split_at_hyphen(pkgver, 1);
+            Some(Package {
+                version,
+                installed: line.contains("[installed"),
+                ..Package::new(&name)
+            })
+        })
+        .collect()
+}
+
+// vim-9.0.2127-r0 description:
+// Improved vi-style text editor
+fn apk_info(output: &str) -> Vec<Package> {
+    let mut packages: Vec<Package> = Vec::new();
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        let Some(pkgver) = line.strip_suffix(" description:") else {
+            continue;
+        };
+        let (name, version) = split_at_hyphen(pkgver, 1);
+        packages.push(Package {
+            version,
+            description: lines.next().and_then(some),
+            installed: true,
+            ..Package::new(&name)
+        });
+    }
+    packages
+}
+
+// vim/jammy-updates,now 2:8.2.3995-1ubuntu2.15 amd64 [installed]
+//   Vi IMproved - enhanced vi editor
+fn apt_list(output: &str) -> Vec<Package> {
+    let mut packages: Vec<Package> = Vec::new();
+    for line in output.lines() {
+        if line.starts_with(char::is_whitespace) {
+            if let Some(package) = packages.last_mut() {
+                package.description = some(line);
+            }
+            continue;
+        }
+        let Some((name, rest)) = line.split_once('/') else {
+            continue;
+        };
+        let mut parts = rest.split_whitespace();
+        let repository = parts
+            .next()
+            .and_then(|repos| repos.split(',').find(|repo| *repo != "now"))
+            .and_then(some);
+        packages.push(Package {
+            version: parts.next().and_then(some),
+            repository,
+            installed: rest.contains("[installed") || rest.contains("[upgradable"),
+            ..Package::new(name)
+        });
+    }
+    packages
+}
+
+// Package: vim
+// Version: 2:8.2.3995-1ubuntu2.15
+fn apt_info(output: &str) -> Vec<Package> {
+    blocks(output)
+        .iter()
+        .filter_map(|block| {
+            let headers: String = block
+                .lines()
+                .filter(|line| !line.starts_with(char::is_whitespace))
+                .map(|line| format!("{}\n", line))
+                .collect();
+            let fields = fields(&headers);
+            let name = field(&fields, "Package")?;
+            Some(Package {
+                version: field(&fields, "Version").and_then(some),
+                repository: field(&fields, "APT-Sources")
+                    .and_then(|sources| sources.split_whitespace().nth(1))
+                    .and_then(some),
+                description: field(&fields, "Description").and_then(some),
+                installed: field(&fields, "APT-Manual-Installed").is_some(),
+                ..Package::new(name)
+            })
+        })
+        .collect()
+}
+
+fn strip_arch(name: &str) -> &str {
+    name.rsplit_once('.').map(|(name, _)| name).unwrap_or(name)
+}
+
+// vim-enhanced.x86_64 : A version of the VIM editor
+fn dnf_search(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with('=') && !line.starts_with("Last metadata"))
+        .filter_map(|line| line.split_once(" : ").or_else(|| line.trim().split_once('\t')))
+        .map(|(name, description)| Package {
+            description: some(description),
+            ..Package::new(strip_arch(name.trim()))
+        })
+        .collect()
+}
+
+// vim-enhanced.x86_64      2:9.0.1677-1.fc38      @updates
+fn dnf_list(output: &str) -> Vec<Package> {
+    let mut installed = true;
+    let mut packages: Vec<Package> = Vec::new();
+    for line in output.lines() {
+        match line.trim() {
+            "Installed Packages" | "Installed packages" => installed = true,
+            "Available Packages" | "Available packages" => installed = false,
+            _ => {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() != 3 || !parts[0].contains('.') {
+                    continue;
+                }
+                packages.push(Package {
+                    version: some(parts[1]),
+                    repository: some(parts[2].trim_start_matches('@')),
+                    installed,
+                    ..Package::new(strip_arch(parts[0]))
+                });
+            }
+        }
+    }
+    packages
+}
+
+// Name         : vim-enhanced
+// Version      : 9.0.1677
+fn dnf_info(output: &str) -> Vec<Package> {
+    let mut installed = false;
+    let mut packages: Vec<Package> = Vec::new();
+    for block in blocks(output).iter() {
+        for line in block.lines() {
+            match line.trim() {
+                "Installed Packages" | "Installed packages" => installed = true,
+                "Available Packages" | "Available packages" => installed = false,
+                _ => (),
+            }
+        }
+        let fields = fields(block);
+        let Some(name) = field(&fields, "Name") else {
+            continue;
+        };
+        let version = match (field(&fields, "Version"), field(&fields, "Release")) {
+            (Some(version), Some(release)) => Some(format!("{}-{}", version, release)),
+            (version, _) => version.and_then(some),
+        };
+        let repository = field(&fields, "From repo")
+            .or_else(|| field(&fields, "Repository"))
+            .map(|repository| repository.trim_start_matches('@'))
+            .and_then(some);
+        packages.push(Package {
+            version,
+            repository,
+            description: field(&fields, "Summary").and_then(some),
+            installed,
+            ..Package::new(name)
+        });
+    }
+    packages
+}
+
+fn flatpak_columns(line: &str) -> Option<Vec<&str>> {
+    if line.starts_with("Name\t") || !line.contains('\t') {
+        return None;
+    }
+    Some(line.split('\t').map(|column| column.trim()).collect())
+}
+
+// Name  Description  Application ID  Version  Branch  Remotes
+fn flatpak_search(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(flatpak_columns)
+        .filter(|columns| columns.len() >= 6)
+        .map(|columns| Package {
+            version: some(columns[3]),
+            repository: some(columns[5]),
+            description: some(columns[1]),
+            ..Package::new(columns[2])
+        })
+        .collect()
+}
+
+// Name  Application ID  Version  Branch  Installation
+fn flatpak_list(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(flatpak_columns)
+        .filter(|columns| columns.len() >= 4)
+        .map(|columns| Package {
+            version: some(columns[2]),
+            description: some(columns[0]),
+            installed: true,
+            ..Package::new(columns[1])
+        })
+        .collect()
+}
+
+// GNU Image Manipulation Program - Create images and edit photographs
+//
+//           ID: org.gimp.GIMP
+fn flatpak_info(output: &str) -> Vec<Package> {
+    let fields = fields(output);
+    let Some(name) = field(&fields, "ID") else {
+        return Vec::new();
+    };
+    let description = output
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .filter(|line| !line.contains(": "))
+        .and_then(|line| line.split_once(" - ").map(|(_, description)| description).or(Some(line)))
+        .and_then(some);
+    vec![Package {
+        version: field(&fields, "Version").and_then(some),
+        repository: field(&fields, "Origin").and_then(some),
+        description,
+        installed: true,
+        ..Package::new(name)
+    }]
+}
+
+// nixpkgs.vim                  vim-9.0.2116
+fn nix_search(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let attr = parts.next()?;
+            let (name, version) = split_at_version(parts.next()?);
+            Some(Package {
+                version,
+                repository: attr.split_once('.').map(|(channel, _)| channel).and_then(some),
+                ..Package::new(&name)
+            })
+        })
+        .collect()
+}
+
+// vim-9.0.2116
+fn nix_list(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|pkgver| {
+            let (name, version) = split_at_version(pkgver);
+            Package { version, installed: true, ..Package::new(&name) }
+        })
+        .collect()
+}
+
+// vim-9.0.2116  The most popular clone of the VI editor
+fn nix_info(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (pkgver, description) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            if pkgver.is_empty() {
+                return None;
+            }
+            let (name, version) = split_at_version(pkgver);
+            Some(Package {
+                version,
+                description: some(description),
+                ..Package::new(&name)
+            })
+        })
+        .collect()
+}
+
+// extra/vim 9.1.0000-1 [installed]
+//     Vi Improved, a highly configurable, improved version of the vi text editor
+fn pacman_search(output: &str) -> Vec<Package> {
+    let mut packages: Vec<Package> = Vec::new();
+    for line in output.lines() {
+        if line.starts_with(char::is_whitespace) {
+            if let Some(package) = packages.last_mut() {
+                package.description = some(line);
+            }
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let Some((repository, name)) = parts.next().and_then(|name| name.split_once('/')) else {
+            continue;
+        };
+        packages.push(Package {
+            version: parts.next().and_then(some),
+            repository: some(repository),
+            installed: line.contains("[installed"),
+            ..Package::new(name)
+        });
+    }
+    packages
+}
+
+// vim 9.1.0000-1
+fn pacman_list(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            Some(Package {
+                version: parts.next().and_then(some),
+                installed: true,
+                ..Package::new(name)
+            })
+        })
+        .collect()
+}
+
+// Repository      : extra
+// Name            : vim
+fn pacman_info(output: &str) -> Vec<Package> {
+    blocks(output)
+        .iter()
+        .filter_map(|block| {
+            let fields = fields(block);
+            let name = field(&fields, "Name")?;
+            Some(Package {
+                version: field(&fields, "Version").and_then(some),
+                repository: field(&fields, "Repository").and_then(some),
+                description: field(&fields, "Description").and_then(some),
+                installed: field(&fields, "Install Date").is_some(),
+                ..Package::new(name)
+            })
+        })
+        .collect()
+}
+
+// Name  Version  Publisher  Notes  Summary
+fn snap_search(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("Name "))
+        .skip(1)
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 4 {
+                return None;
+            }
+            Some(Package {
+                version: some(parts[1]),
+                description: some(&parts[4..].join(" ")),
+                ..Package::new(parts[0])
+            })
+        })
+        .collect()
+}
+
+// Name  Version  Rev  Tracking  Publisher  Notes
+fn snap_list(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("Name "))
+        .skip(1)
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 4 {
+                return None;
+            }
+            Some(Package {
+                version: some(parts[1]),
+                repository: some(parts[3]),
+                installed: true,
+                ..Package::new(parts[0])
+            })
+        })
+        .collect()
+}
+
+// name:      vlc
+// summary:   The ultimate media player
+fn snap_info(output: &str) -> Vec<Package> {
+    let headers: String = output
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let fields = fields(&headers);
+    let Some(name) = field(&fields, "name") else {
+        return Vec::new();
+    };
+    let installed = field(&fields, "installed");
+    vec![Package {
+        version: installed.and_then(|installed| installed.split_whitespace().next()).and_then(some),
+        repository: field(&fields, "tracking").and_then(some),
+        description: field(&fields, "summary").and_then(some),
+        installed: installed.is_some(),
+        ..Package::new(name)
+    }]
+}
+
+// [*] vim-9.0.1672_1   Vim editor (vi clone)
+fn xbps_search(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (state, rest) = line.trim().split_once(char::is_whitespace)?;
+            let rest = rest.trim_start();
+            let (pkgver, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let (name, version) = split_at_hyphen(pkgver, 0);
+            Some(Package {
+                version,
+                description: some(description),
+                installed: state == "[*]",
+                ..Package::new(&name)
+            })
+        })
+        .collect()
+}
+
+// ii vim-9.0.1672_1   Vim editor (vi clone)
+fn xbps_list(output: &str) -> Vec<Package> {
+    xbps_search(output)
+        .into_iter()
+        .map(|package| Package { installed: true, ..package })
+        .collect()
+}
+
+// pkgver: vim-9.0.1672_1
+// short_desc: Vim editor (vi clone)
+fn xbps_info(output: &str) -> Vec<Package> {
+    let fields = fields(output);
+    let Some(pkgver) = field(&fields, "pkgver") else {
+        return Vec::new();
+    };
+    let (name, version) = split_at_hyphen(pkgver, 0);
+    vec![Package {
+        version,
+        repository: field(&fields, "repository").and_then(some),
+        description: field(&fields, "short_desc").and_then(some),
+        installed: field(&fields, "state").is_some_and(|state| state == "installed"),
+        ..Package::new(&name)
+    }]
+}
+
+// S  | Name | Summary | Type
+// i+ | vim  | Vi IMproved | package
+fn zypper_table(output: &str) -> Vec<Package> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("--"))
+        .skip(1)
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('|').map(|column| column.trim()).collect();
+            if columns.len() < 3 || columns[1].is_empty() {
+                return None;
+            }
+            Some(Package {
+                description: some(columns[2]),
+                installed: columns[0].starts_with('i'),
+                ..Package::new(columns[1])
+            })
+        })
+        .collect()
+}
+
+// Repository     : Main Repository (OSS)
+// Name           : vim
+fn zypper_info(output: &str) -> Vec<Package> {
+    let fields = fields(output);
+    let Some(name) = field(&fields, "Name") else {
+        return Vec::new();
+    };
+    vec![Package {
+        version: field(&fields, "Version").and_then(some),
+        repository: field(&fields, "Repository").and_then(some),
+        description: field(&fields, "Summary").and_then(some),
+        installed: field(&fields, "Installed").is_some_and(|installed| installed.eq_ignore_ascii_case("yes")),
+        ..Package::new(name)
+    }]
+}
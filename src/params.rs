@@ -1,8 +1,9 @@
-use std::{env, fmt::Display, fs::File, io::Read, path::PathBuf};
+use std::{env, fmt::Display, fs::File, io::Read, path::{Path, PathBuf}};
 use clap::{ArgAction, Parser, Subcommand};
 use eyre::{eyre, Result};
-use toml::Table;
-use crate::{vendors::PlsCommand, Vendor};
+use toml::{Table, Value};
+use strum::IntoEnumIterator;
+use crate::{vendors::{quote, PlsCommand}, is_root, Aliases, CustomVendor, Elevator, OsRelease, Output, Reason, Root, Vendor, VendorBackend};
 
 
 #[derive(Debug, Parser)]
@@ -25,13 +26,31 @@ pub struct Params {
     pub yes: bool,
 
     #[cfg(not(target_os = "windows"))]
-    /// run as root (user must be sudoer)
+    /// run as root (through sudo, doas, run0, or pkexec)
     #[arg(short, long, global = true, action = ArgAction::SetTrue)]
     pub su: bool,
 
+    #[cfg(not(target_os = "windows"))]
+    /// program used to run as root [default: first found]
+    #[arg(long, global = true, value_enum)]
+    pub elevator: Option<Elevator>,
+
     /// set the installer command
     #[arg(short, long, global = true)]
-    pub vendor: Option<Vendor>,
+    pub vendor: Option<String>,
+
+    /// output format for list, search, and info
+    #[arg(short, long, global = true, value_enum)]
+    pub output: Option<Output>,
+
+    #[arg(skip)]
+    pub vendors: Vec<CustomVendor>,
+
+    #[arg(skip)]
+    pub priority: Vec<String>,
+
+    #[arg(skip)]
+    pub aliases: Aliases,
 
     #[command(subcommand)]
     pub cmd: Cmd,
@@ -42,9 +61,13 @@ pub enum Cmd {
     /// install package(s)
     #[command()]
     Install {
-        /// package(s) to be installed
+        /// package(s) to be installed, or the query with --interactive
         #[arg(name = "PACKAGE")]
         args: Vec<String>,
+
+        /// search and pick the packages to install
+        #[arg(short, long, action = ArgAction::SetTrue)]
+        interactive: bool,
     },
 
     /// remove package(s)
@@ -74,6 +97,14 @@ pub enum Cmd {
         #[arg(short, long, action = ArgAction::SetTrue)]
         paginate: bool,
 
+        /// search every available vendor
+        #[arg(short, long, action = ArgAction::SetTrue)]
+        all_vendors: bool,
+
+        /// pick packages from the results and install them
+        #[arg(long, action = ArgAction::SetTrue)]
+        pick: bool,
+
         #[arg(skip)]
         pager: Option<String>,
     },
@@ -101,15 +132,187 @@ pub enum Cmd {
         pager: Option<String>,
     },
 
+    /// list upgradable packages
+    #[command()]
+    Outdated {
+        /// paginate results
+        #[arg(short, long, action = ArgAction::SetTrue)]
+        paginate: bool,
+
+        #[arg(skip)]
+        pager: Option<String>,
+    },
+
+    /// remove orphaned dependencies
+    #[command()]
+    Autoremove,
+
+    /// clean the package cache
+    #[command()]
+    Clean,
+
+    /// find which installed package owns a file
+    #[command()]
+    Owns {
+        /// file path
+        #[arg(name = "PATH")]
+        args: String,
+    },
+
+    /// find which package provides a file or command
+    #[command()]
+    Provides {
+        /// file or command
+        #[arg(name = "FILE")]
+        args: String,
+    },
+
+    /// list the files installed by a package
+    #[command()]
+    Files {
+        /// installed package
+        #[arg(name = "PACKAGE")]
+        args: String,
+
+        /// paginate results
+        #[arg(short, long, action = ArgAction::SetTrue)]
+        paginate: bool,
+
+        #[arg(skip)]
+        pager: Option<String>,
+    },
+
+    /// keep package(s) from being upgraded
+    #[command()]
+    Hold {
+        /// package(s) to be held
+        #[arg(name = "PACKAGE", required = true)]
+        args: Vec<String>,
+    },
+
+    /// allow held package(s) to be upgraded again
+    #[command()]
+    Unhold {
+        /// package(s) to be released
+        #[arg(name = "PACKAGE", required = true)]
+        args: Vec<String>,
+    },
+
+    /// list held packages
+    #[command()]
+    Holds,
+
+    /// manage package repositories
+    #[command()]
+    Repo {
+        #[command(subcommand)]
+        action: RepoCmd,
+    },
+
+    /// install and upgrade packages to match a manifest
+    #[command()]
+    Apply {
+        /// manifest file
+        #[arg(name = "FILE", default_value = "Pleasefile.toml")]
+        file: String,
+
+        /// also remove installed packages not in the manifest
+        #[arg(long, action = ArgAction::SetTrue)]
+        prune: bool,
+    },
+
+    /// export installed packages as TOML
+    #[command()]
+    Export {
+        /// export from every available vendor
+        #[arg(short, long, action = ArgAction::SetTrue)]
+        all_vendors: bool,
+    },
+
+    /// install packages from an export
+    #[command()]
+    Import {
+        /// file written by export
+        #[arg(name = "FILE")]
+        file: String,
+    },
+
+    /// list the changes made through please
+    #[command()]
+    History {
+        /// only changes to this package
+        #[arg(short, long)]
+        package: Option<String>,
+
+        /// only changes from this date on (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// only changes up to this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// revert a change listed by history, the last one by default
+    #[command()]
+    Undo {
+        /// history entry
+        #[arg(name = "ID")]
+        id: Option<usize>,
+    },
+
     /// list available vendors
     #[command()]
-    ListVendors,
+    ListVendors {
+        /// show the effective command templates
+        #[arg(long, action = ArgAction::SetTrue)]
+        verbose: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Subcommand)]
+pub enum RepoCmd {
+    /// list configured repositories
+    #[command()]
+    List,
+
+    /// add a repository
+    #[command()]
+    Add {
+        /// repository, as the vendor expects it (URL, name, or both)
+        #[arg(name = "REPO", required = true)]
+        args: Vec<String>,
+    },
+
+    /// remove a repository
+    #[command()]
+    Remove {
+        /// repository to be removed
+        #[arg(name = "REPO", required = true)]
+        args: Vec<String>,
+    },
+
+    /// enable a repository
+    #[command()]
+    Enable {
+        /// repository to be enabled
+        #[arg(name = "REPO", required = true)]
+        args: Vec<String>,
+    },
+
+    /// disable a repository
+    #[command()]
+    Disable {
+        /// repository to be disabled
+        #[arg(name = "REPO", required = true)]
+        args: Vec<String>,
+    },
 }
 
 impl Params {
-    pub fn config(mut self) -> Self {
+    pub fn config(mut self) -> Result<Self> {
         if self.skip_settings {
-            return self;
+            return Ok(self);
         }
 
         #[cfg(target_os = "windows")]
@@ -133,13 +336,13 @@ impl Params {
             }
         };
         if config.exists() {
-            let _ = self.load(config);
+            self.load(&config).map_err(|err| eyre!("{}: {}", config.display(), err))?;
         }
 
-        self
+        Ok(self)
     }
 
-    fn load(&mut self, config: PathBuf) -> Result<()> {
+    fn load(&mut self, config: &Path) -> Result<()> {
         let mut file = File::open(config)?;
         let mut content = String::new();
         file.read_to_string(&mut content)?;
@@ -153,30 +356,49 @@ impl Params {
         }
 
         match &self.cmd {
-            Cmd::Search { args, .. } => {
-                if defaults.get("pager").is_some() {
-                    self.cmd = Cmd::Search {
-                        pager: defaults
-                            .get("pager")
-                            .and_then(|pager| pager.as_str())
-                            .map(|pager| pager.to_owned())
-                            .filter(|pager| !pager.is_empty())
-                            .map(|pager| pager.replace("$args", args)),
-                        paginate: true,
-                        args: args.to_owned(),
-                    }
+            Cmd::Search { args, all_vendors, pick, .. } if defaults.get("pager").is_some() => {
+                self.cmd = Cmd::Search {
+                    all_vendors: *all_vendors,
+                    pick: *pick,
+                    pager: defaults
+                        .get("pager")
+                        .and_then(|pager| pager.as_str())
+                        .map(|pager| pager.to_owned())
+                        .filter(|pager| !pager.is_empty())
+                        .map(|pager| pager.replace("$args", &quote(args))),
+                    paginate: true,
+                    args: args.to_owned(),
+                }
+            }
+            Cmd::List { .. } if defaults.get("pager").is_some() => {
+                self.cmd = Cmd::List {
+                    pager: defaults
+                        .get("pager")
+                        .and_then(|pager| pager.as_str())
+                        .map(|pager| pager.to_owned())
+                        .filter(|pager| !pager.is_empty()),
+                    paginate: true,
+                }
+            }
+            Cmd::Files { args, .. } if defaults.get("pager").is_some() => {
+                self.cmd = Cmd::Files {
+                    pager: defaults
+                        .get("pager")
+                        .and_then(|pager| pager.as_str())
+                        .map(|pager| pager.to_owned())
+                        .filter(|pager| !pager.is_empty()),
+                    paginate: true,
+                    args: args.to_owned(),
                 }
             }
-            Cmd::List { .. } => {
-                if defaults.get("pager").is_some() {
-                    self.cmd = Cmd::List {
-                        pager: defaults
-                            .get("pager")
-                            .and_then(|pager| pager.as_str())
-                            .map(|pager| pager.to_owned())
-                            .filter(|pager| !pager.is_empty()),
-                        paginate: true,
-                    }
+            Cmd::Outdated { .. } if defaults.get("pager").is_some() => {
+                self.cmd = Cmd::Outdated {
+                    pager: defaults
+                        .get("pager")
+                        .and_then(|pager| pager.as_str())
+                        .map(|pager| pager.to_owned())
+                        .filter(|pager| !pager.is_empty()),
+                    paginate: true,
                 }
             }
             _ => (),
@@ -188,26 +410,188 @@ impl Params {
         if defaults.get("su").and_then(|yes| yes.as_bool()).unwrap_or_default() {
             self.su = true;
         }
+        #[cfg(not(target_os = "windows"))]
+        if self.elevator.is_none() {
+            if let Some(elevator) = defaults.get("elevator").and_then(|elevator| elevator.as_str()).filter(|elevator| !elevator.is_empty()) {
+                self.elevator = Some(Elevator::try_from(elevator).map_err(|err| eyre![err])?);
+            }
+        }
+        if self.output.is_none() {
+            if let Some(output) = defaults.get("output").and_then(|output| output.as_str()) {
+                self.output = Some(Output::try_from(output).map_err(|err| eyre![err])?);
+            }
+        }
+        if let Some(aliases) = defaults.get("aliases").and_then(|aliases| aliases.as_table()) {
+            self.aliases.load(aliases)?;
+        }
+        match defaults.get("vendors") {
+            // a priority list, read below along with `vendor`
+            Some(Value::Table(_) | Value::Array(_)) | None => (),
+            Some(_) => return Err(eyre!("vendors must be a table of custom vendors or a list of vendor names")),
+        }
+        // `[vendors.apt]` and `[vendor.apt]` are the same
+        for vendors in ["vendors", "vendor"].iter().filter_map(|key| defaults.get(*key).and_then(|vendors| vendors.as_table())) {
+            for (name, table) in vendors.iter() {
+                let table = table.as_table().ok_or_else(|| eyre!("vendor {} must be a table", name))?;
+                self.vendors.push(CustomVendor::from_table(name, table)?);
+            }
+        }
         if self.vendor.is_none() {
             if let Some(vendor) = defaults.get("vendor").and_then(|vendor| vendor.as_str()) {
-                let vendor: Vendor = vendor.try_into().map_err(|err: String| eyre![err])?;
-                self.vendor = Some(vendor);
+                self.vendor = Some(vendor.to_owned());
+            }
+            let priority = defaults.get("vendor").filter(|vendor| vendor.is_array()).or_else(|| defaults.get("vendors"));
+            if let Some(vendors) = priority.and_then(|vendors| vendors.as_array()) {
+                self.priority = vendors
+                    .iter()
+                    .filter_map(|vendor| vendor.as_str())
+                    .map(|vendor| vendor.to_owned())
+                    .collect();
             }
         }
 
         Ok(())
     }
+
+    /// The elevator to run the vendor command with: operations that need
+    /// root are elevated on their own, per-user vendors never are, and the
+    /// others only with `--su`; `None` when already running as root, or on a
+    /// dry run without any elevator installed
+    pub fn elevation(&self, vendor: &dyn VendorBackend, command: PlsCommand) -> Result<Option<Elevator>> {
+        #[cfg(target_os = "windows")]
+        let _ = (vendor, command);
+        #[cfg(target_os = "windows")]
+        return Ok(None);
+
+        #[cfg(not(target_os = "windows"))]
+        match vendor.root(command) {
+            Root::Harmful => {
+                if is_root() {
+                    eprintln!("warning: {} manages per-user packages and should not run as root", vendor.name());
+                } else if self.su {
+                    eprintln!("warning: {} manages per-user packages, not running it as root", vendor.name());
+                }
+                Ok(None)
+            }
+            root if (root == Root::Needed || self.su) && !is_root() => match self.elevator.or_else(Elevator::detect) {
+                Some(elevator) => Ok(Some(elevator)),
+                None if self.dry_run => Ok(None),
+                None => Err(eyre!(
+                    "no elevator installed, candidates are: {}",
+                    Elevator::iter().map(|elevator| elevator.to_string()).collect::<Vec<String>>().join(", "),
+                )),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// All known vendors: the compiled-in ones, with their settings overrides
+    /// applied, followed by those declared in the settings
+    pub fn backends(&self) -> Vec<Box<dyn VendorBackend>> {
+        let mut backends: Vec<Box<dyn VendorBackend>> = Vec::new();
+        for vendor in Vendor::iter() {
+            match self.vendors.iter().find(|custom| custom.name == vendor.name()) {
+                Some(custom) => backends.push(Box::new(custom.clone())),
+                None => backends.push(Box::new(vendor)),
+            }
+        }
+        for vendor in self.vendors.iter() {
+            if Vendor::try_from(vendor.name.as_str()).is_err() {
+                backends.push(Box::new(vendor.clone()));
+            }
+        }
+        backends
+    }
+
+    /// The vendor selected by `--vendor` or the settings, otherwise the first
+    /// available one
+    pub fn backend(&self) -> Result<Box<dyn VendorBackend>> {
+        self.detect().map(|(backend, _)| backend)
+    }
+
+    /// Select the vendor and tell why it was chosen: the one set by
+    /// `--vendor` or the settings, else the first available preferred for
+    /// the distribution, else the first available at all
+    pub fn detect(&self) -> Result<(Box<dyn VendorBackend>, Reason)> {
+        if let Some(name) = &self.vendor {
+            return self.named(name).map(|backend| (backend, Reason::Requested));
+        }
+        if !self.priority.is_empty() {
+            let mut chain = self.chain()?;
+            return Ok((chain.remove(0), Reason::Priority));
+        }
+
+        let backends = self.backends();
+
+        let mut backends: Vec<Box<dyn VendorBackend>> = backends
+            .into_iter()
+            .filter(|backend| backend.is_available())
+            .collect();
+        if let Some((id, preferred)) = OsRelease::load().as_ref().and_then(|release| release.preferred()) {
+            for vendor in preferred.iter() {
+                if let Some(index) = backends.iter().position(|backend| backend.name() == vendor.name()) {
+                    return Ok((backends.remove(index), Reason::Distro(id.to_owned())));
+                }
+            }
+        }
+        if backends.is_empty() {
+            return Err(eyre!(
+                "no vendor installed, candidates are: {}",
+                self.backends().iter().map(|backend| backend.name()).collect::<Vec<String>>().join(", "),
+            ));
+        }
+        Ok((backends.remove(0), Reason::FirstAvailable))
+    }
+
+    /// The available vendors of the settings priority list, in order, or
+    /// only the detected vendor when there is no such list or `--vendor` is
+    /// set; unknown names, such as vendors of other systems, are skipped
+    pub fn chain(&self) -> Result<Vec<Box<dyn VendorBackend>>> {
+        if self.vendor.is_some() || self.priority.is_empty() {
+            return Ok(vec![self.backend()?]);
+        }
+        let mut chain = Vec::new();
+        for name in self.priority.iter() {
+            let Ok(backend) = self.named(name) else {
+                continue;
+            };
+            if backend.is_available() {
+                chain.push(backend);
+            }
+        }
+        if chain.is_empty() {
+            return Err(eyre!("no vendor installed, candidates are: {}", self.priority.join(", ")));
+        }
+        Ok(chain)
+    }
+
+    fn named(&self, name: &str) -> Result<Box<dyn VendorBackend>> {
+        let name = name.to_lowercase();
+        self.backends()
+            .into_iter()
+            .find(|backend| backend.name().to_lowercase() == name)
+            .ok_or_else(|| eyre!("invalid vendor name {}", name))
+    }
 }
 
 impl Cmd {
-    pub fn args(&self) -> String {
+    pub fn args(&self) -> Vec<String> {
         match self {
-            Cmd::Install { args } => args.join(" "),
-            Cmd::Remove { args } => args.join(" "),
-            Cmd::Upgrade { args } => args.join(" "),
-            Cmd::Search { args, .. } => args.to_string(),
-            Cmd::Info { args } => args.to_string(),
-            _ => String::new(),
+            Cmd::Install { args, .. } => args.clone(),
+            Cmd::Remove { args } => args.clone(),
+            Cmd::Upgrade { args } => args.clone(),
+            Cmd::Hold { args } => args.clone(),
+            Cmd::Unhold { args } => args.clone(),
+            Cmd::Search { args, .. } => vec![args.to_string()],
+            Cmd::Info { args } => vec![args.to_string()],
+            Cmd::Owns { args } => vec![args.to_string()],
+            Cmd::Provides { args } => vec![args.to_string()],
+            Cmd::Files { args, .. } => vec![args.to_string()],
+            Cmd::Repo { action: RepoCmd::List } => Vec::new(),
+            Cmd::Repo {
+                action: RepoCmd::Add { args } | RepoCmd::Remove { args } | RepoCmd::Enable { args } | RepoCmd::Disable { args },
+            } => args.clone(),
+            _ => Vec::new(),
         }
     }
 }
@@ -222,7 +606,22 @@ impl Display for Cmd {
             Cmd::Info { .. } => write!(f, "info"),
             Cmd::Update => write!(f, "update"),
             Cmd::List { .. } => write!(f, "list"),
-            Cmd::ListVendors => write!(f, "list-vendors"),
+            Cmd::Outdated { .. } => write!(f, "outdated"),
+            Cmd::Autoremove => write!(f, "autoremove"),
+            Cmd::Clean => write!(f, "clean"),
+            Cmd::Owns { .. } => write!(f, "owns"),
+            Cmd::Provides { .. } => write!(f, "provides"),
+            Cmd::Files { .. } => write!(f, "files"),
+            Cmd::Hold { .. } => write!(f, "hold"),
+            Cmd::Unhold { .. } => write!(f, "unhold"),
+            Cmd::Holds => write!(f, "holds"),
+            Cmd::Repo { .. } => write!(f, "repo"),
+            Cmd::Apply { .. } => write!(f, "apply"),
+            Cmd::Export { .. } => write!(f, "export"),
+            Cmd::Import { .. } => write!(f, "import"),
+            Cmd::History { .. } => write!(f, "history"),
+            Cmd::Undo { .. } => write!(f, "undo"),
+            Cmd::ListVendors { .. } => write!(f, "list-vendors"),
         }
     }
 }
@@ -238,6 +637,20 @@ impl From<&Cmd> for PlsCommand {
             Cmd::Info {..} => PlsCommand::Info,
             Cmd::Update => PlsCommand::Update,
             Cmd::List { .. } => PlsCommand::List,
+            Cmd::Outdated { .. } => PlsCommand::Outdated,
+            Cmd::Autoremove => PlsCommand::Autoremove,
+            Cmd::Clean => PlsCommand::Clean,
+            Cmd::Owns { .. } => PlsCommand::Owns,
+            Cmd::Provides { .. } => PlsCommand::Provides,
+            Cmd::Files { .. } => PlsCommand::Files,
+            Cmd::Hold { .. } => PlsCommand::Hold,
+            Cmd::Unhold { .. } => PlsCommand::Unhold,
+            Cmd::Holds => PlsCommand::Holds,
+            Cmd::Repo { action: RepoCmd::List } => PlsCommand::RepoList,
+            Cmd::Repo { action: RepoCmd::Add { .. } } => PlsCommand::RepoAdd,
+            Cmd::Repo { action: RepoCmd::Remove { .. } } => PlsCommand::RepoRemove,
+            Cmd::Repo { action: RepoCmd::Enable { .. } } => PlsCommand::RepoEnable,
+            Cmd::Repo { action: RepoCmd::Disable { .. } } => PlsCommand::RepoDisable,
             _ => PlsCommand::List,
         }
     }
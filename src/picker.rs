@@ -0,0 +1,146 @@
+use std::io::{self, IsTerminal, Stderr, Write};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{self, ClearType},
+};
+use eyre::{eyre, Result};
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+
+/// Let the user pick entries in a fuzzy-filtered list, returning their
+/// indices, or nothing when cancelled
+///
+/// Typing filters the list, Tab marks the highlighted entry, Enter accepts the
+/// marked entries (or the highlighted one when none is marked), and Esc
+/// cancels.
+pub fn pick(prompt: &str, items: &[String]) -> Result<Vec<usize>> {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Err(eyre!("picking packages requires a terminal"));
+    }
+    let mut stderr = io::stderr();
+    terminal::enable_raw_mode()?;
+    execute!(stderr, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let picked = Picker::new(items).run(prompt, &mut stderr);
+    execute!(stderr, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    picked
+}
+
+struct Picker<'a> {
+    items: &'a [String],
+    matcher: SkimMatcherV2,
+    query: String,
+    matches: Vec<usize>,
+    marked: Vec<usize>,
+    cursor: usize,
+}
+
+impl<'a> Picker<'a> {
+    fn new(items: &'a [String]) -> Self {
+        let mut picker = Self {
+            items,
+            matcher: SkimMatcherV2::default(),
+            query: String::new(),
+            matches: Vec::new(),
+            marked: Vec::new(),
+            cursor: 0,
+        };
+        picker.filter();
+        picker
+    }
+
+    fn run(mut self, prompt: &str, out: &mut Stderr) -> Result<Vec<usize>> {
+        loop {
+            self.draw(prompt, out)?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let control = key.modifiers.contains(KeyModifiers::CONTROL);
+            match key.code {
+                KeyCode::Esc => return Ok(Vec::new()),
+                KeyCode::Char('c') if control => return Ok(Vec::new()),
+                KeyCode::Enter if self.marked.is_empty() => return Ok(self.matches.get(self.cursor).copied().into_iter().collect()),
+                KeyCode::Enter => return Ok(self.marked),
+                KeyCode::Tab => {
+                    if let Some(index) = self.matches.get(self.cursor).copied() {
+                        match self.marked.iter().position(|marked| *marked == index) {
+                            Some(position) => {
+                                self.marked.remove(position);
+                            }
+                            None => self.marked.push(index),
+                        }
+                        self.down();
+                    }
+                }
+                KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+                KeyCode::Char('p') if control => self.cursor = self.cursor.saturating_sub(1),
+                KeyCode::Down => self.down(),
+                KeyCode::Char('n') if control => self.down(),
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.filter();
+                }
+                KeyCode::Char(c) if !control => {
+                    self.query.push(c);
+                    self.filter();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn down(&mut self) {
+        if self.cursor + 1 < self.matches.len() {
+            self.cursor += 1;
+        }
+    }
+
+    /// Keep the entries matching the query, best matches first
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| self.matcher.fuzzy_match(item, &self.query).map(|score| (score, index)))
+            .collect();
+        scored.sort_by(|left, right| right.0.cmp(&left.0).then(left.1.cmp(&right.1)));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.cursor = self.cursor.min(self.matches.len().saturating_sub(1));
+    }
+
+    fn draw(&self, prompt: &str, out: &mut Stderr) -> Result<()> {
+        let (columns, rows) = match terminal::size()? {
+            // some terminals do not report their size
+            (0, _) | (_, 0) => (80, 24),
+            size => size,
+        };
+        let width = columns as usize;
+        let height = rows.saturating_sub(2) as usize;
+        let top = (self.cursor + 1).saturating_sub(height);
+        queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0), Print(format!("{} {}", prompt, self.query)))?;
+        for (row, index) in self.matches.iter().enumerate().skip(top).take(height) {
+            let mark = if self.marked.contains(index) {"[x]"} else {"[ ]"};
+            let line: String = format!("{} {}", mark, self.items[*index]).chars().take(width).collect();
+            queue!(out, cursor::MoveTo(0, (row - top + 1) as u16))?;
+            if row == self.cursor {
+                queue!(out, SetAttribute(Attribute::Reverse), Print(line), SetAttribute(Attribute::Reset))?;
+            } else {
+                queue!(out, Print(line))?;
+            }
+        }
+        let status = format!(
+            "{}/{} · {} marked · tab: mark · enter: accept · esc: cancel",
+            self.matches.len(),
+            self.items.len(),
+            self.marked.len(),
+        );
+        queue!(out, cursor::MoveTo(0, rows.saturating_sub(1)), Print(status.chars().take(width).collect::<String>()))?;
+        out.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::thread;
+use eyre::{eyre, Result};
+use crate::{Package, PlsCommand, VendorBackend};
+
+
+/// Run the search on every vendor at once, returning each vendor’s name and
+/// output in the given order
+pub fn search_all(backends: &[Box<dyn VendorBackend>], query: &str) -> Vec<(String, Result<String>)> {
+    let args = [query.to_owned()];
+    thread::scope(|scope| {
+        let handles: Vec<_> = backends
+            .iter()
+            .filter(|backend| backend.supports(PlsCommand::Search))
+            .map(|backend| {
+                let args = &args;
+                (backend.name(), scope.spawn(move || backend.output(PlsCommand::Search, args)))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|(name, handle)| {
+                let output = handle.join().unwrap_or_else(|_| Err(eyre!("search panicked")));
+                (name, output)
+            })
+            .collect()
+    })
+}
+
+/// Merge search outputs into a single table, each line tagged with the vendor
+/// name
+pub fn merge_results(results: &[(String, Result<String>)]) -> String {
+    let width = results.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+    let mut table = String::new();
+    for (name, output) in results.iter() {
+        match output {
+            Ok(output) => {
+                for line in output.lines().filter(|line| !line.trim().is_empty()) {
+                    table.push_str(&format!("{:<width$}  {}\n", name, line, width = width));
+                }
+            }
+            Err(err) => table.push_str(&format!("{:<width$}  error: {}\n", name, err, width = width)),
+        }
+    }
+    table
+}
+
+/// Parse search outputs into packages tagged with the vendor name, skipping
+/// vendors whose output cannot be parsed
+pub fn parse_results(backends: &[Box<dyn VendorBackend>], results: &[(String, Result<String>)]) -> Vec<(String, Package)> {
+    let mut packages: Vec<(String, Package)> = Vec::new();
+    for (name, output) in results.iter() {
+        let Ok(output) = output else {
+            continue;
+        };
+        let Some(backend) = backends.iter().find(|backend| backend.name() == *name) else {
+            continue;
+        };
+        match backend.parse(PlsCommand::Search, output) {
+            Some(parsed) => packages.extend(parsed.into_iter().map(|package| (name.to_owned(), package))),
+            None => eprintln!("skipping {}: output cannot be parsed", name),
+        }
+    }
+    packages
+}
@@ -1,6 +1,7 @@
 use eyre::{eyre, Result};
 use strum::{EnumIter, IntoEnumIterator};
-use std::{ffi::OsString, fmt::Display, process::Command};
+use crate::{parse_requirement, split_version, Elevator, History, Op, Package, Parser};
+use std::{fmt::Display, io::{self, ErrorKind, Write}, process::{Command, Stdio}, time::Instant};
 
 
 #[derive(Debug, Clone, Copy, EnumIter, PartialEq)]
@@ -59,7 +60,14 @@ pub enum Vendor {
     Termux,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A vendor command ready to run: the program and its argument vector
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandLine {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, EnumIter, PartialEq, Eq, Hash)]
 pub enum PlsCommand {
     Install,
     Remove,
@@ -69,439 +77,1340 @@ pub enum PlsCommand {
     Update,
     UpgradeAll,
     List,
+    Outdated,
+    Autoremove,
+    Clean,
+    Owns,
+    Provides,
+    Files,
+    Hold,
+    Unhold,
+    Holds,
+    RepoList,
+    RepoAdd,
+    RepoRemove,
+    RepoEnable,
+    RepoDisable,
+    InstallFile,
+    Rollback,
+    Available,
+    Explicit,
 }
 
-impl Vendor {
-    pub fn new() -> Result<Self> {
-        for vendor in Vendor::iter() {
-            if vendor.is_available() {
-                return Ok(vendor)
+/// Whether an operation should run as root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Root {
+    /// it changes the system, so please elevates on its own
+    Needed,
+    /// it works on per-user packages, so please never elevates
+    Harmful,
+    /// elevated only with `--su`
+    Optional,
+}
+
+/// A package manager please can drive
+///
+/// Each operation returns its command template, or `None` when the vendor
+/// does not support it. Templates are split on whitespace, except inside
+/// quotes; `$args` expands into the package arguments and `$yes` into the
+/// vendor’s yes flag.
+pub trait VendorBackend: Send + Sync {
+    /// name used to select the vendor
+    fn name(&self) -> String;
+    /// binary looked up in `PATH` to detect the vendor
+    fn binary(&self) -> &str;
+    /// flag that makes the vendor assume yes for all prompts
+    fn yes_flag(&self) -> &str;
+    /// whether the command must, may, or must not run as root
+    fn root(&self, command: PlsCommand) -> Root;
+    /// flag that keeps a package out of `upgrade-all`, with `$args` replaced
+    /// by the package; used to honour please-managed holds
+    fn exclude(&self) -> Option<&str>;
+    /// how the vendor writes a package with a version requirement, with
+    /// `$name` and `$version` replaced; `None` when it cannot honour `op`
+    fn version_format(&self, op: Op) -> Option<&str>;
+
+    fn install(&self) -> Option<&str>;
+    fn remove(&self) -> Option<&str>;
+    fn upgrade(&self) -> Option<&str>;
+    fn search(&self) -> Option<&str>;
+    fn info(&self) -> Option<&str>;
+    fn update(&self) -> Option<&str>;
+    fn upgrade_all(&self) -> Option<&str>;
+    fn list(&self) -> Option<&str>;
+    fn outdated(&self) -> Option<&str>;
+    fn autoremove(&self) -> Option<&str>;
+    fn clean(&self) -> Option<&str>;
+    fn owns(&self) -> Option<&str>;
+    fn provides(&self) -> Option<&str>;
+    fn files(&self) -> Option<&str>;
+    fn hold(&self) -> Option<&str>;
+    fn unhold(&self) -> Option<&str>;
+    fn holds(&self) -> Option<&str>;
+    fn repo_list(&self) -> Option<&str>;
+    fn repo_add(&self) -> Option<&str>;
+    fn repo_remove(&self) -> Option<&str>;
+    fn repo_enable(&self) -> Option<&str>;
+    fn repo_disable(&self) -> Option<&str>;
+    fn install_file(&self) -> Option<&str>;
+    fn rollback(&self) -> Option<&str>;
+    fn available(&self) -> Option<&str>;
+    fn explicit(&self) -> Option<&str>;
+
+    fn template(&self, command: PlsCommand) -> Option<&str> {
+        match command {
+            PlsCommand::Install => self.install(),
+            PlsCommand::Remove => self.remove(),
+            PlsCommand::Upgrade => self.upgrade(),
+            PlsCommand::Search => self.search(),
+            PlsCommand::Info => self.info(),
+            PlsCommand::Update => self.update(),
+            PlsCommand::UpgradeAll => self.upgrade_all(),
+            PlsCommand::List => self.list(),
+            PlsCommand::Outdated => self.outdated(),
+            PlsCommand::Autoremove => self.autoremove(),
+            PlsCommand::Clean => self.clean(),
+            PlsCommand::Owns => self.owns(),
+            PlsCommand::Provides => self.provides(),
+            PlsCommand::Files => self.files(),
+            PlsCommand::Hold => self.hold(),
+            PlsCommand::Unhold => self.unhold(),
+            PlsCommand::Holds => self.holds(),
+            PlsCommand::RepoList => self.repo_list(),
+            PlsCommand::RepoAdd => self.repo_add(),
+            PlsCommand::RepoRemove => self.repo_remove(),
+            PlsCommand::RepoEnable => self.repo_enable(),
+            PlsCommand::RepoDisable => self.repo_disable(),
+            PlsCommand::InstallFile => self.install_file(),
+            PlsCommand::Rollback => self.rollback(),
+            PlsCommand::Available => self.available(),
+            PlsCommand::Explicit => self.explicit(),
+        }
+    }
+
+    fn supports(&self, command: PlsCommand) -> bool {
+        self.template(command).is_some()
+    }
+
+    fn capabilities(&self) -> Vec<PlsCommand> {
+        PlsCommand::iter().filter(|command| self.supports(*command)).collect()
+    }
+
+    fn is_available(&self) -> bool {
+        which::which(self.binary()).is_ok()
+    }
+
+    /// format of the vendor’s search, list, and info output, if known
+    fn parser(&self) -> Option<Parser> {
+        None
+    }
+
+    fn parse(&self, command: PlsCommand, output: &str) -> Option<Vec<Package>> {
+        self.parser()?.parse(command, output)
+    }
+
+    fn command_line(&self, command: PlsCommand, args: &[String], yes: bool) -> Option<CommandLine> {
+        let template = self.template(command)?;
+        CommandLine::from_template(template, args, if yes {self.yes_flag()} else {""})
+    }
+
+    /// Install arguments with version requirements, like `nodejs@18` or
+    /// `openssl>=3`, written the way the vendor expects them
+    fn versioned(&self, args: &[String]) -> Result<Vec<String>> {
+        let mut versioned: Vec<String> = Vec::new();
+        for arg in args.iter() {
+            let (name, requirement) = split_version(arg);
+            if requirement.is_empty() {
+                versioned.push(arg.to_owned());
+                continue;
             }
+            let constraint = parse_requirement(requirement).map_err(|err| eyre!("{}: {}", arg, err))?;
+            if constraint.op == Op::Any {
+                versioned.push(name.to_owned());
+                continue;
+            }
+            let format = self.version_format(constraint.op).ok_or_else(|| {
+                let supported: Vec<&str> = Op::REQUIREMENTS
+                    .into_iter()
+                    .filter(|op| self.version_format(*op).is_some())
+                    .map(|op| op.symbol())
+                    .collect();
+                if supported.is_empty() {
+                    eyre!("{}: {} cannot install specific versions", arg, self.name())
+                } else {
+                    eyre!("{}: {} only supports version requirements with {}", arg, self.name(), supported.join(", "))
+                }
+            })?;
+            versioned.extend(
+                tokens(format)
+                    .into_iter()
+                    .map(|(token, _)| token.replace("$name", name).replace("$version", &constraint.version)),
+            );
         }
-        Err(eyre!(
-            "no vendor installed, candidates are: {}",
-            Vendor::iter().map(|vendor| vendor.to_string()).collect::<Vec<String>>().join(", "),
-        ))
+        Ok(versioned)
+    }
+
+    /// Arguments for `upgrade-all` that leave the held packages untouched
+    fn exclusions(&self, held: &[String]) -> Vec<String> {
+        let Some(template) = self.exclude() else {
+            return Vec::new();
+        };
+        held.iter()
+            .flat_map(|package| tokens(template).into_iter().map(|(token, _)| token.replace("$args", package)))
+            .collect()
     }
 
-    pub fn is_available(&self) -> bool {
-            let vendor_data: VendorData = (*self).into();
-            which::which(vendor_data.1[0]).is_ok()
+    /// Whether the vendor provides the package, checked by quietly running its
+    /// `available` query, or its info when it has none; `None` when the vendor
+    /// cannot tell
+    fn has_package(&self, package: &str) -> Option<bool> {
+        let query = if self.supports(PlsCommand::Available) {PlsCommand::Available} else {PlsCommand::Info};
+        let command = self.command_line(query, &[package.to_owned()], false)?;
+        let status = command.command(None)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        Some(status.map(|status| status.success()).unwrap_or_default())
     }
 
-    pub fn execute(self, command: PlsCommand, args: &str, yes: bool, su: bool, dry_run: bool, pager: Option<String>) -> Result<i32> {
-        let vendor_data: VendorData = self.into();
-        let command = command.format(vendor_data, args, yes, pager);
+    /// Names of the packages installed on request, leaving out dependencies
+    fn explicit_packages(&self) -> Result<Vec<String>> {
+        let output = self.output(PlsCommand::Explicit, &[])?;
+        Ok(output.split_whitespace().map(|name| split_version(name).0.to_owned()).collect())
+    }
+
+    /// Run the command and capture its standard output
+    fn output(&self, command: PlsCommand, args: &[String]) -> Result<String> {
+        let command = self
+            .command_line(command, args, false)
+            .ok_or_else(|| eyre!("command not supported by {}", self.name()))?;
+        let output = command.command(None).stdin(Stdio::null()).stderr(Stdio::null()).output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Run the command and parse its output into packages
+    fn packages(&self, command: PlsCommand, args: &[String]) -> Result<Vec<Package>> {
+        if self.parser().is_none() {
+            return Err(eyre!("output of {} cannot be parsed", self.name()));
+        }
+        let output = self.output(command, args)?;
+        self.parse(command, &output).ok_or_else(|| eyre!("output of {} {} cannot be parsed", self.name(), command))
+    }
 
-        if command.is_empty() {
+    fn execute(&self, command: PlsCommand, args: &[String], yes: bool, su: Option<Elevator>, dry_run: bool, pager: Option<String>) -> Result<i32> {
+        let Some(line) = self.command_line(command, args, yes) else {
             eprintln!("command not supported by the current vendor");
             return Ok(1)
-        }
+        };
 
         if dry_run {
-            eprintln!("{}", command);
+            let elevator = su.map(|elevator| format!("{} ", elevator)).unwrap_or_default();
+            match pager {
+                Some(pager) => eprintln!("{}{} | {}", elevator, line, pager),
+                None => eprintln!("{}{}", elevator, line),
+            }
             return Ok(0);
         }
 
-        #[cfg(target_os = "windows")]
-        let status = Command::new("cmd").args(["/C", &command]).status()?;
-        #[cfg(not(target_os = "windows"))]
-        let status = if su {
-            Command::new("sudo").args(command.split(" ")).status()?
-        } else {
-            Command::new("sh").args(["-c", &command]).status()?
+        let started = Instant::now();
+        let mut child = line.command(su);
+        let status = match pager {
+            Some(pager) => {
+                let mut child = child.stdout(Stdio::piped()).spawn()?;
+                let stdout = child.stdout.take().ok_or_else(|| eyre!("could not pipe {} output", line.program))?;
+                shell(&pager).stdin(stdout).status()?;
+                child.wait()?
+            }
+            None => child.status()?,
         };
+        let status = status.code().unwrap_or_default();
 
-        Ok(status.code().unwrap_or_default())
+        if History::records(command) {
+            // upgrade-all arguments are exclusions, not packages
+            let packages = if command == PlsCommand::UpgradeAll {&[]} else {args};
+            if let Err(err) = History::record(&self.name(), command, packages, status, started.elapsed()) {
+                eprintln!("warning: could not record history: {}", err);
+            }
+        }
+
+        Ok(status)
     }
 }
 
-impl PlsCommand {
-    fn format(self, vendor: VendorData, args: &str, yes: bool, pager: Option<String>) -> String {
+impl Vendor {
+    fn data(&self) -> &'static VendorData {
+        VENDORS
+            .iter()
+            .find(|(vendor, _)| vendor == self)
+            .map(|(_, data)| data)
+            .unwrap_or_else(|| panic!("unreachable code reached for vendor {:?}", self))
+    }
+}
+
+impl VendorBackend for Vendor {
+    fn name(&self) -> String {
+        self.to_string()
+    }
+
+    fn binary(&self) -> &str {
+        self.data().bin
+    }
+
+    fn yes_flag(&self) -> &str {
+        self.data().yes
+    }
+
+    fn root(&self, command: PlsCommand) -> Root {
+        let data = self.data();
+        if data.root.contains(&command) {
+            Root::Needed
+        } else if data.user {
+            Root::Harmful
+        } else {
+            Root::Optional
+        }
+    }
+
+    fn exclude(&self) -> Option<&str> {
+        self.data().exclude
+    }
+
+    fn version_format(&self, op: Op) -> Option<&str> {
+        self.data()
+            .versions
+            .iter()
+            .find(|(symbol, _)| *symbol == op.symbol())
+            .map(|(_, format)| *format)
+    }
+
+    fn install(&self) -> Option<&str> {
+        self.data().install
+    }
+
+    fn remove(&self) -> Option<&str> {
+        self.data().remove
+    }
+
+    fn upgrade(&self) -> Option<&str> {
+        self.data().upgrade
+    }
+
+    fn search(&self) -> Option<&str> {
+        self.data().search
+    }
+
+    fn info(&self) -> Option<&str> {
+        self.data().info
+    }
+
+    fn update(&self) -> Option<&str> {
+        self.data().update
+    }
+
+    fn upgrade_all(&self) -> Option<&str> {
+        self.data().upgrade_all
+    }
+
+    fn list(&self) -> Option<&str> {
+        self.data().list
+    }
+
+    fn outdated(&self) -> Option<&str> {
+        self.data().outdated
+    }
+
+    fn autoremove(&self) -> Option<&str> {
+        self.data().autoremove
+    }
+
+    fn clean(&self) -> Option<&str> {
+        self.data().clean
+    }
+
+    fn owns(&self) -> Option<&str> {
+        self.data().owns
+    }
+
+    fn provides(&self) -> Option<&str> {
+        self.data().provides
+    }
+
+    fn files(&self) -> Option<&str> {
+        self.data().files
+    }
+
+    fn hold(&self) -> Option<&str> {
+        self.data().hold
+    }
+
+    fn unhold(&self) -> Option<&str> {
+        self.data().unhold
+    }
+
+    fn holds(&self) -> Option<&str> {
+        self.data().holds
+    }
+
+    fn repo_list(&self) -> Option<&str> {
+        self.data().repo_list
+    }
+
+    fn repo_add(&self) -> Option<&str> {
+        self.data().repo_add
+    }
+
+    fn repo_remove(&self) -> Option<&str> {
+        self.data().repo_remove
+    }
+
+    fn repo_enable(&self) -> Option<&str> {
+        self.data().repo_enable
+    }
+
+    fn repo_disable(&self) -> Option<&str> {
+        self.data().repo_disable
+    }
+
+    fn install_file(&self) -> Option<&str> {
+        self.data().install_file
+    }
+
+    fn rollback(&self) -> Option<&str> {
+        self.data().rollback
+    }
+
+    fn available(&self) -> Option<&str> {
+        self.data().available
+    }
+
+    fn explicit(&self) -> Option<&str> {
+        self.data().explicit
+    }
+
+    fn parser(&self) -> Option<Parser> {
         match self {
-            PlsCommand::Install => vendor.1[2].to_owned(),
-            PlsCommand::Remove => vendor.1[3].to_owned(),
-            PlsCommand::Upgrade => vendor.1[4].to_owned(),
-            PlsCommand::Info => vendor.1[6].to_owned(),
-            PlsCommand::Update => vendor.1[7].to_owned(),
-            PlsCommand::UpgradeAll => vendor.1[8].to_owned(),
-            PlsCommand::Search => {
-                if let Some(pager) = pager {
-                    format!("{} | {}", vendor.1[5], pager)
-                } else {
-                    vendor.1[5].to_owned()
-                }
-            }
-            PlsCommand::List => {
-                if let Some(pager) = pager {
-                    format!("{} | {}", vendor.1[9], pager)
-                } else {
-                    vendor.1[9].to_owned()
-                }
+            #[cfg(target_os = "linux")]
+            Apk => Some(Parser::Apk),
+            #[cfg(target_os = "linux")]
+            Apt => Some(Parser::Apt),
+            #[cfg(target_os = "linux")]
+            Dnf | Yum => Some(Parser::Dnf),
+            #[cfg(target_os = "linux")]
+            Flatpak => Some(Parser::Flatpak),
+            #[cfg(target_os = "linux")]
+            NixEnv => Some(Parser::NixEnv),
+            #[cfg(target_os = "linux")]
+            Pacman | Yay => Some(Parser::Pacman),
+            #[cfg(target_os = "linux")]
+            Snap => Some(Parser::Snap),
+            #[cfg(target_os = "linux")]
+            Xbps => Some(Parser::Xbps),
+            #[cfg(target_os = "linux")]
+            Zypper => Some(Parser::Zypper),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+}
+
+impl CommandLine {
+    /// Split a template into argv, expanding `$args` into one element per
+    /// argument and `$yes` into the vendor’s yes flag (or nothing)
+    ///
+    /// Quoted text stays a single element, where only `$yes` is replaced, so
+    /// templates can hand a pipeline to `sh -c` without exposing `$args` to
+    /// the shell.
+    fn from_template(template: &str, args: &[String], yes: &str) -> Option<Self> {
+        let mut argv: Vec<String> = Vec::new();
+        for (token, quoted) in tokens(template) {
+            match token.as_str() {
+                "$args" if !quoted => argv.extend(args.iter().cloned()),
+                "$yes" if !quoted => argv.extend(yes.split_whitespace().map(|flag| flag.to_owned())),
+                _ if quoted => argv.push(token.replace("$yes", yes)),
+                _ => argv.push(token),
             }
         }
-            .replace("$yes", if yes {vendor.1[1]} else {""})
-            .replace("$args", args)
+        if argv.is_empty() {
+            return None;
+        }
+        let program = argv.remove(0);
+        Some(Self { program, args: argv })
+    }
+
+    fn command(&self, su: Option<Elevator>) -> Command {
+        #[cfg(target_os = "windows")]
+        let _ = su;
+        #[cfg(target_os = "windows")]
+        let mut command = {
+            let mut command = Command::new("cmd");
+            command.arg("/C").arg(&self.program);
+            command
+        };
+        #[cfg(not(target_os = "windows"))]
+        let mut command = match su {
+            Some(elevator) => elevator.command(&self.program),
+            None => Command::new(&self.program),
+        };
+        command.args(&self.args);
+        command
     }
 }
 
-impl From<OsString> for Vendor {
-    fn from(value: OsString) -> Self {
-        let value = value.to_string_lossy().to_lowercase();
-        for vendor in Vendor::iter() {
-            if vendor.to_string().to_lowercase() == value {
-                return vendor;
+impl Display for CommandLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", quote(&self.program))?;
+        for arg in self.args.iter() {
+            write!(f, " {}", quote(arg))?;
+        }
+        Ok(())
+    }
+}
+
+/// Split on whitespace, keeping single- or double-quoted text together
+fn tokens(template: &str) -> Vec<(String, bool)> {
+    let mut tokens: Vec<(String, bool)> = Vec::new();
+    let mut current: Option<(String, bool)> = None;
+    let mut quote: Option<char> = None;
+    for c in template.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert((String::new(), true)).0.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert((String::new(), true)).1 = true;
             }
+            (None, c) if c.is_whitespace() => tokens.extend(current.take()),
+            (None, c) => current.get_or_insert((String::new(), false)).0.push(c),
         }
-        panic!("invalid vendor name {}", value);
+    }
+    tokens.extend(current);
+    tokens
+}
+
+/// Quote an argument so it can be safely pasted into a shell line
+pub(crate) fn quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_owned()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
     }
 }
 
+/// Print the text, through the pager if any
+pub fn page(text: &str, pager: Option<String>) -> Result<()> {
+    let Some(pager) = pager else {
+        return match io::stdout().write_all(text.as_bytes()) {
+            Err(err) if err.kind() != ErrorKind::BrokenPipe => Err(err.into()),
+            _ => Ok(()),
+        };
+    };
+    let mut child = shell(&pager).stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager may quit before reading everything
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// Only pagers go through the shell, since they are user-supplied pipelines
+fn shell(line: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    let command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", line]);
+        command
+    };
+    #[cfg(not(target_os = "windows"))]
+    let command = {
+        let mut command = Command::new("sh");
+        command.args(["-c", line]);
+        command
+    };
+    command
+}
+
 //----------------------------------------------------------------------------//
 use Vendor::*;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-struct VendorData(Vendor, [&'static str; 10]);
-
-static VENDORS: &[VendorData] = &[
-    #[cfg(target_os = "linux")]
-    VendorData(Apk, [
-        "apk",
-        "",
-        "apk add $args",
-        "apk del $args",
-        "apk upgrade $args",
-        "apk search $args",
-        "apk info $args",
-        "apk update",
-        "apk upgrade",
-        "apk list --installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Apt, [
-        "apt",
-        "--yes",
-        "apt install $yes $args",
-        "apt remove $yes $args",
-        "apt install --only-upgrade $yes $args",
-        "apt search $args",
-        "apt show $args",
-        "apt update $yes",
-        "apt upgrade $yes",
-        "apt list --installed",
-    ]),
+struct VendorData {
+    bin: &'static str,
+    yes: &'static str,
+    /// operations that must run as root
+    root: &'static [PlsCommand],
+    /// manages per-user packages, so running the operations not in `root` as
+    /// root is harmful
+    user: bool,
+    exclude: Option<&'static str>,
+    versions: &'static [(&'static str, &'static str)],
+    install: Option<&'static str>,
+    remove: Option<&'static str>,
+    upgrade: Option<&'static str>,
+    search: Option<&'static str>,
+    info: Option<&'static str>,
+    update: Option<&'static str>,
+    upgrade_all: Option<&'static str>,
+    list: Option<&'static str>,
+    outdated: Option<&'static str>,
+    autoremove: Option<&'static str>,
+    clean: Option<&'static str>,
+    owns: Option<&'static str>,
+    provides: Option<&'static str>,
+    files: Option<&'static str>,
+    hold: Option<&'static str>,
+    unhold: Option<&'static str>,
+    holds: Option<&'static str>,
+    repo_list: Option<&'static str>,
+    repo_add: Option<&'static str>,
+    repo_remove: Option<&'static str>,
+    repo_enable: Option<&'static str>,
+    repo_disable: Option<&'static str>,
+    install_file: Option<&'static str>,
+    rollback: Option<&'static str>,
+    available: Option<&'static str>,
+    explicit: Option<&'static str>,
+}
+
+const UNSUPPORTED: VendorData = VendorData {
+    bin: "",
+    yes: "",
+    root: &[],
+    user: false,
+    exclude: None,
+    versions: &[],
+    install: None,
+    remove: None,
+    upgrade: None,
+    search: None,
+    info: None,
+    update: None,
+    upgrade_all: None,
+    list: None,
+    outdated: None,
+    autoremove: None,
+    clean: None,
+    owns: None,
+    provides: None,
+    files: None,
+    hold: None,
+    unhold: None,
+    holds: None,
+    repo_list: None,
+    repo_add: None,
+    repo_remove: None,
+    repo_enable: None,
+    repo_disable: None,
+    install_file: None,
+    rollback: None,
+    available: None,
+    explicit: None,
+};
+
+// operations that change the system, for vendors managing system packages
+const SYSTEM: &[PlsCommand] = &[
+    PlsCommand::Install,
+    PlsCommand::Remove,
+    PlsCommand::Upgrade,
+    PlsCommand::Update,
+    PlsCommand::UpgradeAll,
+    PlsCommand::Autoremove,
+    PlsCommand::Clean,
+    PlsCommand::InstallFile,
+    PlsCommand::Rollback,
+    PlsCommand::Hold,
+    PlsCommand::Unhold,
+    PlsCommand::RepoAdd,
+    PlsCommand::RepoRemove,
+    PlsCommand::RepoEnable,
+    PlsCommand::RepoDisable,
+];
+
+// every entry ends with `..UNSUPPORTED`, so new operations only touch the
+// vendors that actually support them
+#[allow(clippy::needless_update)]
+static VENDORS: &[(Vendor, VendorData)] = &[
+    #[cfg(target_os = "linux")]
+    (Apk, VendorData {
+        bin: "apk",
+        yes: "",
+        root: SYSTEM,
+        versions: &[
+            ("=", "$name=$version"),
+            ("@", "$name~$version"),
+            (">=", "$name>=$version"),
+            (">", "$name>$version"),
+            ("<=", "$name<=$version"),
+            ("<", "$name<$version"),
+        ],
+        install: Some("apk add $args"),
+        remove: Some("apk del $args"),
+        upgrade: Some("apk upgrade $args"),
+        search: Some("apk search $args"),
+        info: Some("apk info $args"),
+        update: Some("apk update"),
+        upgrade_all: Some("apk upgrade"),
+        list: Some("apk list --installed"),
+        outdated: Some("apk version -l <"),
+        clean: Some("apk cache clean"),
+        owns: Some("apk info --who-owns $args"),
+        files: Some("apk info -L $args"),
+        hold: Some("sh -c 'for p do v=$(apk list -I \"$p\" | cut -d\" \" -f1); apk add \"$p=${v#\"$p\"-}\" || exit; done' sh $args"),
+        unhold: Some("apk add $args"),
+        holds: Some("grep -E [=<>~] /etc/apk/world"),
+        repo_list: Some("cat /etc/apk/repositories"),
+        repo_add: Some("sh -c 'printf \"%s\\n\" \"$@\" >> /etc/apk/repositories' sh $args"),
+        repo_remove: Some("sh -c 'f=/etc/apk/repositories; for r do export r; awk \"\\$0 != ENVIRON[\\\"r\\\"]\" \"$f\" > \"$f.new\" && cat \"$f.new\" > \"$f\" && rm \"$f.new\" || exit; done' sh $args"),
+        repo_enable: Some("sh -c 'f=/etc/apk/repositories; for r do export r; awk \"\\$0 == \\\"#\\\" ENVIRON[\\\"r\\\"] { \\$0 = ENVIRON[\\\"r\\\"] } 1\" \"$f\" > \"$f.new\" && cat \"$f.new\" > \"$f\" && rm \"$f.new\" || exit; done' sh $args"),
+        repo_disable: Some("sh -c 'f=/etc/apk/repositories; for r do export r; awk \"\\$0 == ENVIRON[\\\"r\\\"] { \\$0 = \\\"#\\\" \\$0 } 1\" \"$f\" > \"$f.new\" && cat \"$f.new\" > \"$f\" && rm \"$f.new\" || exit; done' sh $args"),
+        install_file: Some("apk add --allow-untrusted $args"),
+        explicit: Some("cat /etc/apk/world"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Apt, VendorData {
+        bin: "apt",
+        yes: "--yes",
+        root: SYSTEM,
+        versions: &[
+            ("=", "$name=$version"),
+            ("@", "$name=$version*"),
+        ],
+        install: Some("apt install $yes $args"),
+        remove: Some("apt remove $yes $args"),
+        upgrade: Some("apt install --only-upgrade $yes $args"),
+        search: Some("apt search $args"),
+        info: Some("apt show $args"),
+        update: Some("apt update $yes"),
+        upgrade_all: Some("apt upgrade $yes"),
+        list: Some("apt list --installed"),
+        outdated: Some("apt list --upgradable"),
+        autoremove: Some("apt autoremove $yes"),
+        clean: Some("apt clean"),
+        owns: Some("dpkg -S $args"),
+        provides: Some("apt-file search $args"),
+        files: Some("dpkg -L $args"),
+        hold: Some("apt-mark hold $args"),
+        unhold: Some("apt-mark unhold $args"),
+        holds: Some("apt-mark showhold"),
+        repo_list: Some("grep -rhsE --include=*.list --include=*.sources ^(deb|URIs:) /etc/apt"),
+        repo_add: Some("add-apt-repository $yes $args"),
+        repo_remove: Some("add-apt-repository --remove $yes $args"),
+        install_file: Some("apt install $yes $args"),
+        explicit: Some("apt-mark showmanual"),
+        ..UNSUPPORTED
+    }),
     #[cfg(target_os = "macos")]
-    VendorData(Brew, [
-        "brew",
-        "",
-        "brew install $args",
-        "brew uninstall $args",
-        "brew upgrade $args",
-        "brew search $args",
-        "brew info $args",
-        "brew update",
-        "brew upgrade",
-        "brew list",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Cards, [
-        "cards",
-        "",
-        "cards install $args",
-        "cards remove $args",
-        "cards install --upgrade $args",
-        "cards search $args",
-        "cards info $args",
-        "cards sync",
-        "cards upgrade",
-        "cards list",
-    ]),
+    (Brew, VendorData {
+        bin: "brew",
+        yes: "",
+        user: true,
+        versions: &[
+            ("@", "$name@$version"),
+        ],
+        install: Some("brew install $args"),
+        remove: Some("brew uninstall $args"),
+        upgrade: Some("brew upgrade $args"),
+        search: Some("brew search $args"),
+        info: Some("brew info $args"),
+        update: Some("brew update"),
+        upgrade_all: Some("brew upgrade"),
+        list: Some("brew list"),
+        outdated: Some("brew outdated"),
+        autoremove: Some("brew autoremove"),
+        clean: Some("brew cleanup"),
+        files: Some("brew list $args"),
+        hold: Some("brew pin $args"),
+        unhold: Some("brew unpin $args"),
+        holds: Some("brew list --pinned"),
+        repo_list: Some("brew tap"),
+        repo_add: Some("brew tap $args"),
+        repo_remove: Some("brew untap $args"),
+        explicit: Some("brew leaves --installed-on-request"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Cards, VendorData {
+        bin: "cards",
+        yes: "",
+        root: SYSTEM,
+        install: Some("cards install $args"),
+        remove: Some("cards remove $args"),
+        upgrade: Some("cards install --upgrade $args"),
+        search: Some("cards search $args"),
+        info: Some("cards info $args"),
+        update: Some("cards sync"),
+        upgrade_all: Some("cards upgrade"),
+        list: Some("cards list"),
+        outdated: Some("cards diff"),
+        ..UNSUPPORTED
+    }),
     #[cfg(target_os = "windows")]
-    VendorData(Choco, [
-        "choco",
-        "--yes",
-        "choco install $yes $args",
-        "choco uninstall $yes $args",
-        "choco upgrade $yes $args",
-        "choco search $args",
-        "choco info $args",
-        "",
-        "choco upgrade all $yes",
-        "choco list",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Dnf, [
-        "dnf",
-        "--assumeyes",
-        "dnf install $yes $args",
-        "dnf remove $yes $args",
-        "dnf upgrade $yes $args",
-        "dnf search $args",
-        "dnf info $args",
-        "dnf check-update $yes",
-        "dnf update $yes",
-        "dnf list --installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Emerge, [
-        "emerge",
-        "",
-        "emerge $args",
-        "emerge --depclean $args",
-        "emerge --update $args",
-        "emerge --search $args",
-        "emerge --info $args",
-        "emerge --sync",
-        "emerge -vuDN @world",
-        "qlist -Iv",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Eopkg, [
-        "eopkg",
-        "--yes-all",
-        "eopkg install $yes $args",
-        "eopkg remove $yes $args",
-        "eopkg upgrade $yes $args",
-        "eopkg search $args",
-        "eopkg info $args",
-        "eopkg update-repo $yes",
-        "eopkg upgrade $yes",
-        "eopkg list-installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Flatpak, [
-        "flatpak",
-        "--assumeyes",
-        "flatpak --user install $yes $args",
-        "flatpak --user uninstall $yes $args",
-        "flatpak --user update $yes $args",
-        "flatpak --user search $args",
-        "flatpak --user info $args",
-        "",
-        "flatpak --user update $yes",
-        "flatpak --user list",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Guix, [
-        "guix",
-        "",
-        "guix install $yes $args",
-        "guix remove $yes $args",
-        "guix upgrade $yes $args",
-        "guix search $args",
-        "guix show $args",
-        "guix refresh $yes",
-        "guix upgrade $yes",
-        "guix package --list-installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(NixEnv, [
-        "nix-env",
-        "",
-        "nix-env --install $args",
-        "nix-env --uninstall $args",
-        "nix-env --upgrade $args",
-        "nix-env -qaP $args",
-        "nix-env -qa --description $args",
-        "nix-channel --update",
-        "nix-env --upgrade",
-        "nix-env --query --installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Opkg, [
-        "opkg",
-        "",
-        "opkg install $args",
-        "opkg remove $args",
-        "opkg upgrade $args",
-        "opkg find $args",
-        "opkg info $args",
-        "opkg update",
-        "opkg upgrade",
-        "opkg list-installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Pacman, [
-        "pacman",
-        "--noconfirm",
-        "pacman -S $yes $args",
-        "pacman -Rs $yes $args",
-        "pacman -S $yes $args",
-        "pacman -Ss $args",
-        "pacman -Si $args",
-        "pacman -Sy $yes",
-        "pacman -Syu $yes",
-        "pacman -Q",
-    ]),
+    (Choco, VendorData {
+        bin: "choco",
+        yes: "--yes",
+        versions: &[
+            ("=", "$name --version $version"),
+        ],
+        install: Some("choco install $yes $args"),
+        remove: Some("choco uninstall $yes $args"),
+        upgrade: Some("choco upgrade $yes $args"),
+        search: Some("choco search $args"),
+        info: Some("choco info $args"),
+        upgrade_all: Some("choco upgrade all $yes"),
+        list: Some("choco list"),
+        outdated: Some("choco outdated"),
+        repo_list: Some("choco source list"),
+        repo_remove: Some("choco source remove --name $args"),
+        repo_enable: Some("choco source enable --name $args"),
+        repo_disable: Some("choco source disable --name $args"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Dnf, VendorData {
+        bin: "dnf",
+        yes: "--assumeyes",
+        root: SYSTEM,
+        versions: &[
+            ("=", "$name-$version"),
+            ("@", "$name-$version*"),
+        ],
+        install: Some("dnf install $yes $args"),
+        remove: Some("dnf remove $yes $args"),
+        upgrade: Some("dnf upgrade $yes $args"),
+        search: Some("dnf search $args"),
+        info: Some("dnf info $args"),
+        // check-update exits with 100 when updates are available
+        update: Some("sh -c 'dnf check-update $yes; status=$?; [ $status -eq 100 ] && exit 0; exit $status'"),
+        upgrade_all: Some("dnf update $yes"),
+        list: Some("dnf list --installed"),
+        outdated: Some("sh -c 'dnf check-update; status=$?; [ $status -eq 100 ] && exit 0; exit $status'"),
+        autoremove: Some("dnf autoremove $yes"),
+        clean: Some("dnf clean all"),
+        owns: Some("rpm -qf $args"),
+        provides: Some("dnf provides $args"),
+        files: Some("rpm -ql $args"),
+        hold: Some("dnf versionlock add $args"),
+        unhold: Some("dnf versionlock delete $args"),
+        holds: Some("dnf versionlock list"),
+        repo_list: Some("dnf repolist --all"),
+        repo_add: Some("dnf config-manager --add-repo $args"),
+        repo_enable: Some("dnf config-manager --set-enabled $args"),
+        repo_disable: Some("dnf config-manager --set-disabled $args"),
+        install_file: Some("dnf install $yes $args"),
+        rollback: Some("dnf history undo last $yes"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Emerge, VendorData {
+        bin: "emerge",
+        yes: "",
+        root: SYSTEM,
+        exclude: Some("--exclude $args"),
+        versions: &[
+            ("=", "=$name-$version"),
+            ("@", "=$name-$version*"),
+            (">=", ">=$name-$version"),
+            (">", ">$name-$version"),
+            ("<=", "<=$name-$version"),
+            ("<", "<$name-$version"),
+        ],
+        install: Some("emerge $args"),
+        remove: Some("emerge --depclean $args"),
+        upgrade: Some("emerge --update $args"),
+        search: Some("emerge --search $args"),
+        info: Some("emerge --info $args"),
+        update: Some("emerge --sync"),
+        upgrade_all: Some("emerge -vuDN @world $args"),
+        list: Some("qlist -Iv"),
+        outdated: Some("emerge --pretend --update --deep --newuse @world"),
+        autoremove: Some("emerge --depclean"),
+        clean: Some("eclean distfiles"),
+        owns: Some("qfile $args"),
+        provides: Some("e-file $args"),
+        files: Some("qlist $args"),
+        repo_list: Some("eselect repository list -i"),
+        repo_add: Some("eselect repository add $args"),
+        repo_remove: Some("eselect repository remove $args"),
+        repo_enable: Some("eselect repository enable $args"),
+        repo_disable: Some("eselect repository disable $args"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Eopkg, VendorData {
+        bin: "eopkg",
+        yes: "--yes-all",
+        root: SYSTEM,
+        exclude: Some("--exclude $args"),
+        install: Some("eopkg install $yes $args"),
+        remove: Some("eopkg remove $yes $args"),
+        upgrade: Some("eopkg upgrade $yes $args"),
+        search: Some("eopkg search $args"),
+        info: Some("eopkg info $args"),
+        update: Some("eopkg update-repo $yes"),
+        upgrade_all: Some("eopkg upgrade $yes $args"),
+        list: Some("eopkg list-installed"),
+        outdated: Some("eopkg list-upgrades"),
+        autoremove: Some("eopkg remove-orphans $yes"),
+        clean: Some("eopkg delete-cache"),
+        owns: Some("eopkg search-file $args"),
+        repo_list: Some("eopkg list-repo"),
+        repo_add: Some("eopkg add-repo $args"),
+        repo_remove: Some("eopkg remove-repo $args"),
+        repo_enable: Some("eopkg enable-repo $args"),
+        repo_disable: Some("eopkg disable-repo $args"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Flatpak, VendorData {
+        bin: "flatpak",
+        yes: "--assumeyes",
+        user: true,
+        install: Some("flatpak --user install $yes $args"),
+        remove: Some("flatpak --user uninstall $yes $args"),
+        upgrade: Some("flatpak --user update $yes $args"),
+        search: Some("flatpak --user search $args"),
+        info: Some("flatpak --user info $args"),
+        upgrade_all: Some("flatpak --user update $yes"),
+        list: Some("flatpak --user list"),
+        outdated: Some("flatpak --user remote-ls --updates"),
+        autoremove: Some("flatpak --user uninstall --unused $yes"),
+        files: Some("sh -c 'find \"$(flatpak --user info --show-location \"$1\")/files\" -type f' sh $args"),
+        hold: Some("flatpak --user mask $args"),
+        unhold: Some("flatpak --user mask --remove $args"),
+        holds: Some("flatpak --user mask"),
+        repo_list: Some("flatpak --user remotes"),
+        repo_add: Some("flatpak --user remote-add --if-not-exists $args"),
+        repo_remove: Some("flatpak --user remote-delete $args"),
+        repo_enable: Some("flatpak --user remote-modify --enable $args"),
+        repo_disable: Some("flatpak --user remote-modify --disable $args"),
+        install_file: Some("sh -c 'for ref do flatpak --user install $yes --from \"$ref\" || exit; done' sh $args"),
+        rollback: Some("sh -c 'for ref do commit=$(flatpak --user info \"$ref\" | sed -n \"s/^ *Parent: *//p\"); [ -n \"$commit\" ] && [ \"$commit\" != - ] || { echo \"$ref: no previous commit\" >&2; exit 1; }; flatpak --user update $yes --commit=\"$commit\" \"$ref\" || exit; done' sh $args"),
+        available: Some("sh -c 'flatpak --user search --columns=application \"$1\" | grep -qxF \"$1\"' sh $args"),
+        explicit: Some("flatpak --user list --app --columns=application"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Guix, VendorData {
+        bin: "guix",
+        yes: "",
+        user: true,
+        exclude: Some("--do-not-upgrade=^$args$"),
+        versions: &[
+            ("=", "$name@$version"),
+            ("@", "$name@$version"),
+        ],
+        install: Some("guix install $yes $args"),
+        remove: Some("guix remove $yes $args"),
+        upgrade: Some("guix upgrade $yes $args"),
+        search: Some("guix search $args"),
+        info: Some("guix show $args"),
+        update: Some("guix refresh $yes"),
+        upgrade_all: Some("guix upgrade $yes $args"),
+        list: Some("guix package --list-installed"),
+        outdated: Some("guix upgrade --dry-run"),
+        clean: Some("guix gc"),
+        rollback: Some("guix package --roll-back"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (NixEnv, VendorData {
+        bin: "nix-env",
+        yes: "",
+        user: true,
+        versions: &[
+            ("=", "$name-$version"),
+        ],
+        install: Some("nix-env --install $args"),
+        remove: Some("nix-env --uninstall $args"),
+        upgrade: Some("nix-env --upgrade $args"),
+        search: Some("nix-env -qaP $args"),
+        info: Some("nix-env -qa --description $args"),
+        update: Some("nix-channel --update"),
+        upgrade_all: Some("nix-env --upgrade"),
+        list: Some("nix-env --query --installed"),
+        outdated: Some("nix-env --upgrade --dry-run"),
+        clean: Some("nix-collect-garbage --delete-old"),
+        provides: Some("nix-locate $args"),
+        repo_list: Some("nix-channel --list"),
+        repo_add: Some("nix-channel --add $args"),
+        repo_remove: Some("nix-channel --remove $args"),
+        rollback: Some("nix-env --rollback"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Opkg, VendorData {
+        bin: "opkg",
+        yes: "",
+        root: SYSTEM,
+        install: Some("opkg install $args"),
+        remove: Some("opkg remove $args"),
+        upgrade: Some("opkg upgrade $args"),
+        search: Some("opkg find $args"),
+        info: Some("opkg info $args"),
+        update: Some("opkg update"),
+        upgrade_all: Some("opkg upgrade"),
+        list: Some("opkg list-installed"),
+        outdated: Some("opkg list-upgradable"),
+        owns: Some("opkg search $args"),
+        files: Some("opkg files $args"),
+        hold: Some("opkg flag hold $args"),
+        unhold: Some("opkg flag user $args"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Pacman, VendorData {
+        bin: "pacman",
+        yes: "--noconfirm",
+        root: SYSTEM,
+        exclude: Some("--ignore $args"),
+        install: Some("pacman -S $yes $args"),
+        remove: Some("pacman -Rs $yes $args"),
+        upgrade: Some("pacman -S $yes $args"),
+        search: Some("pacman -Ss $args"),
+        info: Some("pacman -Si $args"),
+        update: Some("pacman -Sy $yes"),
+        upgrade_all: Some("pacman -Syu $yes $args"),
+        list: Some("pacman -Q"),
+        outdated: Some("pacman -Qu"),
+        autoremove: Some("sh -c \"pacman -Qdtq | pacman -Rns $yes -\""),
+        clean: Some("pacman -Sc $yes"),
+        owns: Some("pacman -Qo $args"),
+        provides: Some("pacman -F $args"),
+        files: Some("pacman -Ql $args"),
+        repo_list: Some("pacman-conf --repo-list"),
+        install_file: Some("pacman -U $yes $args"),
+        explicit: Some("pacman -Qqe"),
+        ..UNSUPPORTED
+    }),
     #[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "dragonfly", target_os = "netbsd"))]
-    VendorData(Pkg, [
-        "pkg",
-        "--yes",
-        "pkg install $yes $args",
-        "pkg remove $yes $args",
-        "pkg install $yes $args",
-        "pkg search $args",
-        "pkg info $args",
-        "pkg update $yes",
-        "pkg upgrade $yes",
-        "pkg info --all",
-    ]),
+    (Pkg, VendorData {
+        bin: "pkg",
+        yes: "--yes",
+        root: SYSTEM,
+        install: Some("pkg install $yes $args"),
+        remove: Some("pkg remove $yes $args"),
+        upgrade: Some("pkg install $yes $args"),
+        search: Some("pkg search $args"),
+        info: Some("pkg info $args"),
+        update: Some("pkg update $yes"),
+        upgrade_all: Some("pkg upgrade $yes"),
+        list: Some("pkg info --all"),
+        outdated: Some("pkg version -vRl <"),
+        autoremove: Some("pkg autoremove $yes"),
+        clean: Some("pkg clean $yes"),
+        owns: Some("pkg which $args"),
+        files: Some("pkg info -l $args"),
+        hold: Some("pkg lock $yes $args"),
+        unhold: Some("pkg unlock $yes $args"),
+        holds: Some("pkg lock -l"),
+        ..UNSUPPORTED
+    }),
     #[cfg(target_os = "haiku")]
-    VendorData(Pkgman, [
-        "pkgman",
-        "-y",
-        "pkgman install $yes $args",
-        "pkgman uninstall $yes $args",
-        "pkgman update $yes $args",
-        "pkgman search $args",
-        "",
-        "pkgman refresh $yes",
-        "pkgman update $yes",
-        "pkgman search --installed-only --all",
-    ]),
+    (Pkgman, VendorData {
+        bin: "pkgman",
+        yes: "-y",
+        install: Some("pkgman install $yes $args"),
+        remove: Some("pkgman uninstall $yes $args"),
+        upgrade: Some("pkgman update $yes $args"),
+        search: Some("pkgman search $args"),
+        update: Some("pkgman refresh $yes"),
+        upgrade_all: Some("pkgman update $yes"),
+        list: Some("pkgman search --installed-only --all"),
+        repo_list: Some("pkgman list-repos"),
+        repo_add: Some("pkgman add-repo $args"),
+        repo_remove: Some("pkgman drop-repo $args"),
+        ..UNSUPPORTED
+    }),
     #[cfg(target_os = "macos")]
-    VendorData(Ports, [
-        "prt-get",
-        "",
-        "prt-get install $args",
-        "prt-get remove $args",
-        "prt-get update $args",
-        "prt-get search $args",
-        "prt-get info $args",
-        "ports -u",
-        "prt-get sysup",
-        "prt-get listinst",
-    ]),
+    (Ports, VendorData {
+        bin: "prt-get",
+        yes: "",
+        root: SYSTEM,
+        install: Some("prt-get install $args"),
+        remove: Some("prt-get remove $args"),
+        upgrade: Some("prt-get update $args"),
+        search: Some("prt-get search $args"),
+        info: Some("prt-get info $args"),
+        update: Some("ports -u"),
+        upgrade_all: Some("prt-get sysup"),
+        list: Some("prt-get listinst"),
+        outdated: Some("prt-get diff"),
+        ..UNSUPPORTED
+    }),
     #[cfg(target_os = "windows")]
-    VendorData(Scoop, [
-        "scoop",
-        "",
-        "scoop install $args",
-        "scoop uninstall $args",
-        "scoop update $args",
-        "scoop search $args",
-        "scoop info $args",
-        "scoop update",
-        "scoop update *",
-        "scoop list",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Slackpkg, [
-        "slackpkg",
-        "",
-        "slackpkg install $args",
-        "slackpkg remove $args",
-        "slackpkg upgrade $args",
-        "slackpkg search $args",
-        "slackpkg info $args",
-        "slackpkg update",
-        "slackpkg upgrade-all",
-        "ls -1 /var/log/packages",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Snap, [
-        "snap",
-        "",
-        "snap install --classic $args",
-        "snap remove $args",
-        "snap refresh $args",
-        "snap find $args",
-        "snap info $args",
-        "",
-        "snap refresh",
-        "snap list",
-    ]),
+    (Scoop, VendorData {
+        bin: "scoop",
+        yes: "",
+        user: true,
+        versions: &[
+            ("=", "$name@$version"),
+        ],
+        install: Some("scoop install $args"),
+        remove: Some("scoop uninstall $args"),
+        upgrade: Some("scoop update $args"),
+        search: Some("scoop search $args"),
+        info: Some("scoop info $args"),
+        update: Some("scoop update"),
+        upgrade_all: Some("scoop update *"),
+        list: Some("scoop list"),
+        outdated: Some("scoop status"),
+        clean: Some("scoop cleanup *"),
+        hold: Some("scoop hold $args"),
+        unhold: Some("scoop unhold $args"),
+        repo_list: Some("scoop bucket list"),
+        repo_add: Some("scoop bucket add $args"),
+        repo_remove: Some("scoop bucket rm $args"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Slackpkg, VendorData {
+        bin: "slackpkg",
+        yes: "",
+        root: SYSTEM,
+        install: Some("slackpkg install $args"),
+        remove: Some("slackpkg remove $args"),
+        upgrade: Some("slackpkg upgrade $args"),
+        search: Some("slackpkg search $args"),
+        info: Some("slackpkg info $args"),
+        update: Some("slackpkg update"),
+        upgrade_all: Some("slackpkg upgrade-all"),
+        list: Some("ls -1 /var/log/packages"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Snap, VendorData {
+        bin: "snap",
+        yes: "",
+        root: SYSTEM,
+        install: Some("snap install --classic $args"),
+        remove: Some("snap remove $args"),
+        upgrade: Some("snap refresh $args"),
+        search: Some("snap find $args"),
+        info: Some("snap info $args"),
+        upgrade_all: Some("snap refresh"),
+        list: Some("snap list"),
+        outdated: Some("snap refresh --list"),
+        files: Some("sh -c 'find \"/snap/$1/current\" -type f' sh $args"),
+        hold: Some("snap refresh --hold $args"),
+        unhold: Some("snap refresh --unhold $args"),
+        holds: Some("sh -c 'snap list | grep -w held'"),
+        install_file: Some("snap install --dangerous $args"),
+        ..UNSUPPORTED
+    }),
     #[cfg(target_os = "android")]
-    VendorData(Termux, [
-        "termux",
-        "--yes",
-        "pkg install $yes $args",
-        "pkg uninstall $yes $args",
-        "pkg install $yes $args",
-        "pkg search $args",
-        "pkg show $args",
-        "pkg update $yes",
-        "pkg upgrade $yes",
-        "pkg list-installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Urpm, [
-        "urpm",
-        "",
-        "urpmi $args",
-        "urpme $args",
-        "urpmi $args",
-        "urpmq --fuzzy $args",
-        "urpmq -i $args",
-        "urpmi.update -a",
-        "urpmi --auto-update",
-        "rpm --query --all",
-    ]),
+    (Termux, VendorData {
+        bin: "termux",
+        yes: "--yes",
+        user: true,
+        versions: &[
+            ("=", "$name=$version"),
+            ("@", "$name=$version*"),
+        ],
+        install: Some("pkg install $yes $args"),
+        remove: Some("pkg uninstall $yes $args"),
+        upgrade: Some("pkg install $yes $args"),
+        search: Some("pkg search $args"),
+        info: Some("pkg show $args"),
+        update: Some("pkg update $yes"),
+        upgrade_all: Some("pkg upgrade $yes"),
+        list: Some("pkg list-installed"),
+        outdated: Some("apt list --upgradable"),
+        autoremove: Some("apt autoremove $yes"),
+        clean: Some("pkg clean"),
+        owns: Some("dpkg -S $args"),
+        provides: Some("apt-file search $args"),
+        files: Some("dpkg -L $args"),
+        hold: Some("apt-mark hold $args"),
+        unhold: Some("apt-mark unhold $args"),
+        holds: Some("apt-mark showhold"),
+        install_file: Some("apt install $yes $args"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Urpm, VendorData {
+        bin: "urpm",
+        yes: "",
+        root: SYSTEM,
+        versions: &[
+            ("=", "$name-$version"),
+        ],
+        install: Some("urpmi $args"),
+        remove: Some("urpme $args"),
+        upgrade: Some("urpmi $args"),
+        search: Some("urpmq --fuzzy $args"),
+        info: Some("urpmq -i $args"),
+        update: Some("urpmi.update -a"),
+        upgrade_all: Some("urpmi --auto-update"),
+        list: Some("rpm --query --all"),
+        outdated: Some("urpmq --auto-select"),
+        autoremove: Some("urpme --auto-orphans"),
+        clean: Some("urpmi --clean"),
+        owns: Some("rpm -qf $args"),
+        provides: Some("urpmf $args"),
+        files: Some("rpm -ql $args"),
+        repo_list: Some("urpmq --list-media"),
+        repo_add: Some("urpmi.addmedia $args"),
+        repo_remove: Some("urpmi.removemedia $args"),
+        repo_enable: Some("urpmi.update --no-ignore $args"),
+        repo_disable: Some("urpmi.update --ignore $args"),
+        install_file: Some("urpmi $args"),
+        ..UNSUPPORTED
+    }),
     #[cfg(target_os = "windows")]
-    VendorData(Winget, [
-        "winget",
-        "",
-        "winget install $args",
-        "winget uninstall $args",
-        "winget upgrade $args",
-        "winget search $args",
-        "winget show $args",
-        "",
-        "winget upgrade --all",
-        "winget list",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Xbps, [
-        "xbps",
-        "--yes",
-        "xbps-install $yes $args",
-        "xbps-remove $yes $args",
-        "xbps-install --update $yes $args",
-        "xbps-query -Rs $args",
-        "xbps-query -RS $args",
-        "xbps-install --sync $yes",
-        "xbps-install --update $yes",
-        "xbps-query --list-pkgs",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Yay, [
-        "yay",
-        "--noconfirm",
-        "yay --topdown --cleanafter -S $yes $args",
-        "pacman -Rs $yes $args",
-        "yay --topdown --cleanafter -S $yes $args",
-        "yay --topdown -Ss $args",
-        "yay --topdown -Si $args",
-        "yay --topdown -Sy $yes",
-        "yay --topdown -Syu $yes",
-        "pacman -Q",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Yum, [
-        "yum",
-        "--assumeyes",
-        "yum install $yes $args",
-        "yum remove $yes $args",
-        "yum update $yes $args",
-        "yum search $args",
-        "yum info $args",
-        "yum check-update $yes",
-        "yum update $yes",
-        "yum list --installed",
-    ]),
-    #[cfg(target_os = "linux")]
-    VendorData(Zypper, [
-        "zypper",
-        "--no-confirm",
-        "zypper install $yes $args",
-        "zypper remove $yes $args",
-        "zypper update $yes $args",
-        "zypper search $args",
-        "zypper info $args",
-        "zypper refresh $yes",
-        "zypper update $yes",
-        "zypper search --installed-only",
-    ]),
+    (Winget, VendorData {
+        bin: "winget",
+        yes: "",
+        versions: &[
+            ("=", "$name --version $version"),
+        ],
+        install: Some("winget install $args"),
+        remove: Some("winget uninstall $args"),
+        upgrade: Some("winget upgrade $args"),
+        search: Some("winget search $args"),
+        info: Some("winget show $args"),
+        upgrade_all: Some("winget upgrade --all"),
+        list: Some("winget list"),
+        outdated: Some("winget upgrade"),
+        hold: Some("winget pin add $args"),
+        unhold: Some("winget pin remove $args"),
+        holds: Some("winget pin list"),
+        repo_list: Some("winget source list"),
+        repo_add: Some("winget source add $args"),
+        repo_remove: Some("winget source remove $args"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Xbps, VendorData {
+        bin: "xbps",
+        yes: "--yes",
+        root: SYSTEM,
+        versions: &[
+            (">=", "$name>=$version"),
+            (">", "$name>$version"),
+            ("<=", "$name<=$version"),
+            ("<", "$name<$version"),
+        ],
+        install: Some("xbps-install $yes $args"),
+        remove: Some("xbps-remove $yes $args"),
+        upgrade: Some("xbps-install --update $yes $args"),
+        search: Some("xbps-query -Rs $args"),
+        info: Some("xbps-query -RS $args"),
+        update: Some("xbps-install --sync $yes"),
+        upgrade_all: Some("xbps-install --update $yes"),
+        list: Some("xbps-query --list-pkgs"),
+        outdated: Some("xbps-install --update --dry-run"),
+        autoremove: Some("xbps-remove --remove-orphans $yes"),
+        clean: Some("xbps-remove --clean-cache $yes"),
+        owns: Some("xbps-query -o $args"),
+        provides: Some("xbps-query -Ro $args"),
+        files: Some("xbps-query -f $args"),
+        hold: Some("xbps-pkgdb -m hold $args"),
+        unhold: Some("xbps-pkgdb -m unhold $args"),
+        holds: Some("xbps-query -H"),
+        repo_list: Some("xbps-query -L"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Yay, VendorData {
+        bin: "yay",
+        yes: "--noconfirm",
+        // removing goes through pacman
+        root: &[PlsCommand::Remove],
+        user: true,
+        exclude: Some("--ignore $args"),
+        install: Some("yay --topdown --cleanafter -S $yes $args"),
+        remove: Some("pacman -Rs $yes $args"),
+        upgrade: Some("yay --topdown --cleanafter -S $yes $args"),
+        search: Some("yay --topdown -Ss $args"),
+        info: Some("yay --topdown -Si $args"),
+        update: Some("yay --topdown -Sy $yes"),
+        upgrade_all: Some("yay --topdown -Syu $yes $args"),
+        list: Some("pacman -Q"),
+        outdated: Some("yay -Qu"),
+        autoremove: Some("yay -Yc $yes"),
+        clean: Some("yay -Sc $yes"),
+        owns: Some("pacman -Qo $args"),
+        provides: Some("pacman -F $args"),
+        files: Some("pacman -Ql $args"),
+        repo_list: Some("pacman-conf --repo-list"),
+        install_file: Some("yay -U $yes $args"),
+        explicit: Some("yay -Qqe"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Yum, VendorData {
+        bin: "yum",
+        yes: "--assumeyes",
+        root: SYSTEM,
+        versions: &[
+            ("=", "$name-$version"),
+            ("@", "$name-$version*"),
+        ],
+        install: Some("yum install $yes $args"),
+        remove: Some("yum remove $yes $args"),
+        upgrade: Some("yum update $yes $args"),
+        search: Some("yum search $args"),
+        info: Some("yum info $args"),
+        // check-update exits with 100 when updates are available
+        update: Some("sh -c 'yum check-update $yes; status=$?; [ $status -eq 100 ] && exit 0; exit $status'"),
+        upgrade_all: Some("yum update $yes"),
+        list: Some("yum list --installed"),
+        outdated: Some("sh -c 'yum check-update; status=$?; [ $status -eq 100 ] && exit 0; exit $status'"),
+        autoremove: Some("yum autoremove $yes"),
+        clean: Some("yum clean all"),
+        owns: Some("rpm -qf $args"),
+        provides: Some("yum provides $args"),
+        files: Some("rpm -ql $args"),
+        hold: Some("yum versionlock add $args"),
+        unhold: Some("yum versionlock delete $args"),
+        holds: Some("yum versionlock list"),
+        repo_list: Some("yum repolist all"),
+        repo_add: Some("yum-config-manager --add-repo $args"),
+        repo_enable: Some("yum-config-manager --enable $args"),
+        repo_disable: Some("yum-config-manager --disable $args"),
+        install_file: Some("yum install $yes $args"),
+        rollback: Some("yum history undo last $yes"),
+        ..UNSUPPORTED
+    }),
+    #[cfg(target_os = "linux")]
+    (Zypper, VendorData {
+        bin: "zypper",
+        yes: "--no-confirm",
+        root: SYSTEM,
+        versions: &[
+            ("=", "$name=$version"),
+            (">=", "$name>=$version"),
+            (">", "$name>$version"),
+            ("<=", "$name<=$version"),
+            ("<", "$name<$version"),
+        ],
+        install: Some("zypper install $yes $args"),
+        remove: Some("zypper remove $yes $args"),
+        upgrade: Some("zypper update $yes $args"),
+        search: Some("zypper search $args"),
+        info: Some("zypper info $args"),
+        update: Some("zypper refresh $yes"),
+        upgrade_all: Some("zypper update $yes"),
+        list: Some("zypper search --installed-only"),
+        outdated: Some("zypper list-updates"),
+        clean: Some("zypper clean --all"),
+        owns: Some("rpm -qf $args"),
+        provides: Some("zypper search --provides $args"),
+        files: Some("rpm -ql $args"),
+        hold: Some("zypper addlock $args"),
+        unhold: Some("zypper removelock $args"),
+        holds: Some("zypper locks"),
+        repo_list: Some("zypper repos"),
+        repo_add: Some("zypper addrepo $args"),
+        repo_remove: Some("zypper removerepo $args"),
+        repo_enable: Some("zypper modifyrepo --enable $args"),
+        repo_disable: Some("zypper modifyrepo --disable $args"),
+        install_file: Some("zypper install $yes $args"),
+        ..UNSUPPORTED
+    }),
 ];
 
 impl Display for Vendor {
@@ -510,14 +1419,49 @@ impl Display for Vendor {
     }
 }
 
-impl From<Vendor> for VendorData {
-    fn from(value: Vendor) -> Self {
-        for vendor in VENDORS.iter() {
-            if vendor.0 == value {
-                return *vendor;
+impl Display for PlsCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlsCommand::Install => write!(f, "install"),
+            PlsCommand::Remove => write!(f, "remove"),
+            PlsCommand::Upgrade => write!(f, "upgrade"),
+            PlsCommand::Search => write!(f, "search"),
+            PlsCommand::Info => write!(f, "info"),
+            PlsCommand::Update => write!(f, "update"),
+            PlsCommand::UpgradeAll => write!(f, "upgrade-all"),
+            PlsCommand::List => write!(f, "list"),
+            PlsCommand::Outdated => write!(f, "outdated"),
+            PlsCommand::Autoremove => write!(f, "autoremove"),
+            PlsCommand::Clean => write!(f, "clean"),
+            PlsCommand::Owns => write!(f, "owns"),
+            PlsCommand::Provides => write!(f, "provides"),
+            PlsCommand::Files => write!(f, "files"),
+            PlsCommand::Hold => write!(f, "hold"),
+            PlsCommand::Unhold => write!(f, "unhold"),
+            PlsCommand::Holds => write!(f, "holds"),
+            PlsCommand::RepoList => write!(f, "repo-list"),
+            PlsCommand::RepoAdd => write!(f, "repo-add"),
+            PlsCommand::RepoRemove => write!(f, "repo-remove"),
+            PlsCommand::RepoEnable => write!(f, "repo-enable"),
+            PlsCommand::RepoDisable => write!(f, "repo-disable"),
+            PlsCommand::InstallFile => write!(f, "install-file"),
+            PlsCommand::Rollback => write!(f, "rollback"),
+            PlsCommand::Available => write!(f, "available"),
+            PlsCommand::Explicit => write!(f, "explicit"),
+        }
+    }
+}
+
+impl TryFrom<&str> for PlsCommand {
+    type Error = String;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        for command in PlsCommand::iter() {
+            if command.to_string() == value {
+                return Ok(command);
             }
         }
-        panic!("unreachable code reached for vendor {:?}", value);
+        Err(format!("invalid command {}", value))
     }
 }
 
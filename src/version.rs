@@ -0,0 +1,162 @@
+use std::{cmp::Ordering, fmt::Display};
+
+
+/// A version requirement such as `>=3`, `<2.0`, `=1.2.3`, or a bare `18.19`,
+/// which matches any version starting with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub op: Op,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Any,
+    Prefix,
+    Eq,
+    Ge,
+    Gt,
+    Le,
+    Lt,
+}
+
+impl Constraint {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value.is_empty() || value == "*" {
+            return Ok(Self { op: Op::Any, version: String::new() });
+        }
+        let (op, version) = [(">=", Op::Ge), ("<=", Op::Le), ("==", Op::Eq), (">", Op::Gt), ("<", Op::Lt), ("=", Op::Eq)]
+            .iter()
+            .find_map(|(prefix, op)| value.strip_prefix(prefix).map(|version| (*op, version.trim())))
+            .unwrap_or((Op::Prefix, value));
+        if version.is_empty() {
+            return Err(format!("invalid version constraint {}", value));
+        }
+        Ok(Self { op, version: version.to_owned() })
+    }
+
+    /// The install argument asking for the package at this version, like
+    /// `nodejs@18` or `openssl>=3`
+    pub fn requirement(&self, name: &str) -> String {
+        match self.op {
+            Op::Any => name.to_owned(),
+            op => format!("{}{}{}", name, op.symbol(), self.version),
+        }
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        let ordering = compare(version, &self.version);
+        match self.op {
+            Op::Any => true,
+            Op::Prefix => {
+                let version = strip_epoch(version);
+                version == self.version
+                    || version.strip_prefix(&self.version).is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_alphanumeric()))
+            }
+            Op::Eq => ordering == Ordering::Equal,
+            Op::Ge => ordering != Ordering::Less,
+            Op::Gt => ordering == Ordering::Greater,
+            Op::Le => ordering != Ordering::Greater,
+            Op::Lt => ordering == Ordering::Less,
+        }
+    }
+}
+
+impl Op {
+    /// Operators that actually require a version
+    pub const REQUIREMENTS: [Op; 6] = [Op::Prefix, Op::Eq, Op::Ge, Op::Gt, Op::Le, Op::Lt];
+
+    /// Symbol used in install arguments and vendor version tables; `@` is a
+    /// version prefix, as in `nodejs@18`
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Op::Any => "*",
+            Op::Prefix => "@",
+            Op::Eq => "=",
+            Op::Ge => ">=",
+            Op::Gt => ">",
+            Op::Le => "<=",
+            Op::Lt => "<",
+        }
+    }
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.op {
+            Op::Any => write!(f, "*"),
+            Op::Prefix => write!(f, "{}", self.version),
+            Op::Eq => write!(f, "={}", self.version),
+            Op::Ge => write!(f, ">={}", self.version),
+            Op::Gt => write!(f, ">{}", self.version),
+            Op::Le => write!(f, "<={}", self.version),
+            Op::Lt => write!(f, "<{}", self.version),
+        }
+    }
+}
+
+/// Split an install argument such as `nodejs@18` or `openssl>=3` into the
+/// package name and the version requirement, if any
+pub fn split_version(arg: &str) -> (&str, &str) {
+    // skip the first character, so scoped names like `@types/node` stay whole
+    match arg.char_indices().skip(1).find(|(_, c)| "@<>=".contains(*c)) {
+        Some((index, _)) => arg.split_at(index),
+        None => (arg, ""),
+    }
+}
+
+/// Parse the requirement part of [`split_version`]; `@` introduces a plain
+/// version, a prefix unless it has an operator
+pub fn parse_requirement(requirement: &str) -> Result<Constraint, String> {
+    let requirement = requirement.strip_prefix('@').unwrap_or(requirement);
+    if requirement.trim().is_empty() {
+        return Err("missing version after the package name".to_owned());
+    }
+    Constraint::parse(requirement)
+}
+
+/// Compare versions segment by segment, numbers numerically and everything
+/// else lexically, ignoring any `epoch:` prefix
+pub fn compare(left: &str, right: &str) -> Ordering {
+    let left = segments(strip_epoch(left));
+    let right = segments(strip_epoch(right));
+    for (left, right) in left.iter().zip(right.iter()) {
+        let ordering = match (left.parse::<u64>(), right.parse::<u64>()) {
+            (Ok(left), Ok(right)) => left.cmp(&right),
+            _ => left.cmp(right),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    left.len().cmp(&right.len())
+}
+
+fn strip_epoch(version: &str) -> &str {
+    match version.split_once(':') {
+        Some((epoch, rest)) if epoch.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => version,
+    }
+}
+
+fn segments(version: &str) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for c in version.chars() {
+        let same_kind = current.chars().last().is_some_and(|last| last.is_ascii_digit() == c.is_ascii_digit());
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                segments.push(std::mem::take(&mut current));
+            }
+        } else if current.is_empty() || same_kind {
+            current.push(c);
+        } else {
+            segments.push(std::mem::replace(&mut current, c.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
@@ -0,0 +1,179 @@
+use please_install::{Package, Parser, PlsCommand};
+
+
+fn parse(parser: Parser, command: PlsCommand, fixture: &str) -> Vec<Package> {
+    parser.parse(command, fixture).expect("command should be parsed")
+}
+
+fn package(name: &str, version: Option<&str>, repository: Option<&str>, description: Option<&str>, installed: bool) -> Package {
+    Package {
+        name: name.to_owned(),
+        version: version.map(|version| version.to_owned()),
+        repository: repository.map(|repository| repository.to_owned()),
+        description: description.map(|description| description.to_owned()),
+        installed,
+    }
+}
+
+#[test]
+fn unparsed_commands() {
+    assert_eq!(Parser::Apt.parse(PlsCommand::Install, ""), None);
+    assert_eq!(Parser::try_from("nixenv"), Ok(Parser::NixEnv));
+}
+
+#[test]
+fn apk() {
+    let search = parse(Parser::Apk, PlsCommand::Search, include_str!("fixtures/apk-search.txt"));
+    assert_eq!(search.len(), 3);
+    assert_eq!(search[1], package("vim-doc", Some("9.0.2127-r0"), None, None, false));
+
+    let list = parse(Parser::Apk, PlsCommand::List, include_str!("fixtures/apk-list.txt"));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[0], package("alpine-base", Some("3.19.0-r0"), None, None, true));
+
+    let info = parse(Parser::Apk, PlsCommand::Info, include_str!("fixtures/apk-info.txt"));
+    assert_eq!(info, vec![package("vim", Some("9.0.2127-r0"), None, Some("Improved vi-style text editor"), true)]);
+}
+
+#[test]
+fn apt() {
+    let search = parse(Parser::Apt, PlsCommand::Search, include_str!("fixtures/apt-search.txt"));
+    assert_eq!(search, vec![
+        package("vim", Some("2:9.0.1378-2+deb12u2"), Some("oldstable"), Some("Vi IMproved - enhanced vi editor"), true),
+        package("vim-addon-manager", Some("0.5.10"), Some("oldstable"), Some("manager of addons for the Vim editor"), false),
+    ]);
+
+    let list = parse(Parser::Apt, PlsCommand::List, include_str!("fixtures/apt-list.txt"));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[2], package("curl", Some("7.88.1-10+deb12u7"), Some("oldstable-security"), None, true));
+
+    let info = parse(Parser::Apt, PlsCommand::Info, include_str!("fixtures/apt-info.txt"));
+    assert_eq!(info, vec![package("apt", Some("2.6.1"), Some("bookworm/main"), Some("commandline package manager"), true)]);
+}
+
+#[test]
+fn dnf() {
+    let search = parse(Parser::Dnf, PlsCommand::Search, include_str!("fixtures/dnf-search.txt"));
+    assert_eq!(search.len(), 3);
+    assert_eq!(search[0], package(
+        "vim-enhanced",
+        None,
+        None,
+        Some("A version of the VIM editor which includes recent enhancements"),
+        false,
+    ));
+
+    let list = parse(Parser::Dnf, PlsCommand::List, include_str!("fixtures/dnf-list.txt"));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[1], package("bash", Some("5.2.15-3.fc38"), Some("updates"), None, true));
+
+    let info = parse(Parser::Dnf, PlsCommand::Info, include_str!("fixtures/dnf-info.txt"));
+    assert_eq!(info.len(), 2);
+    assert_eq!((info[0].version.as_deref(), info[0].repository.as_deref(), info[0].installed), (Some("9.0.1677-1.fc38"), Some("updates"), true));
+    assert_eq!((info[1].version.as_deref(), info[1].repository.as_deref(), info[1].installed), (Some("9.1.031-1.fc38"), Some("updates"), false));
+}
+
+#[test]
+fn flatpak() {
+    let search = parse(Parser::Flatpak, PlsCommand::Search, include_str!("fixtures/flatpak-search.txt"));
+    assert_eq!(search.len(), 2);
+    assert_eq!(search[0], package("org.gimp.GIMP", Some("2.10.36"), Some("flathub"), Some("Create images and edit photographs"), false));
+
+    let list = parse(Parser::Flatpak, PlsCommand::List, include_str!("fixtures/flatpak-list.txt"));
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0], package("org.freedesktop.Platform", Some("23.08.11"), None, Some("Freedesktop Platform"), true));
+
+    let info = parse(Parser::Flatpak, PlsCommand::Info, include_str!("fixtures/flatpak-info.txt"));
+    assert_eq!(info, vec![package("org.gimp.GIMP", Some("2.10.36"), Some("flathub"), Some("Create images and edit photographs"), true)]);
+}
+
+#[test]
+fn nix_env() {
+    let search = parse(Parser::NixEnv, PlsCommand::Search, include_str!("fixtures/nix-env-search.txt"));
+    assert_eq!(search[1], package("vim-full", Some("9.0.2116"), Some("nixpkgs"), None, false));
+
+    let list = parse(Parser::NixEnv, PlsCommand::List, include_str!("fixtures/nix-env-list.txt"));
+    assert_eq!(list, vec![
+        package("nix", Some("2.18.1"), None, None, true),
+        package("vim", Some("9.0.2116"), None, None, true),
+    ]);
+
+    let info = parse(Parser::NixEnv, PlsCommand::Info, include_str!("fixtures/nix-env-info.txt"));
+    assert_eq!(info, vec![package("vim", Some("9.0.2116"), None, Some("The most popular clone of the VI editor"), false)]);
+}
+
+#[test]
+fn pacman() {
+    let search = parse(Parser::Pacman, PlsCommand::Search, include_str!("fixtures/pacman-search.txt"));
+    assert_eq!(search.len(), 2);
+    assert_eq!(search[1], package(
+        "vim",
+        Some("9.1.0000-1"),
+        Some("extra"),
+        Some("Vi Improved, a highly configurable, improved version of the vi text editor"),
+        true,
+    ));
+
+    let list = parse(Parser::Pacman, PlsCommand::List, include_str!("fixtures/pacman-list.txt"));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[2], package("vim", Some("9.1.0000-1"), None, None, true));
+
+    let info = parse(Parser::Pacman, PlsCommand::Info, include_str!("fixtures/pacman-info.txt"));
+    assert_eq!(info, vec![package(
+        "vim",
+        Some("9.1.0000-1"),
+        Some("extra"),
+        Some("Vi Improved, a highly configurable, improved version of the vi text editor"),
+        false,
+    )]);
+}
+
+#[test]
+fn snap() {
+    let search = parse(Parser::Snap, PlsCommand::Search, include_str!("fixtures/snap-search.txt"));
+    assert_eq!(search.len(), 2);
+    assert_eq!(search[0], package("vlc", Some("3.0.20"), None, Some("The ultimate media player"), false));
+
+    let list = parse(Parser::Snap, PlsCommand::List, include_str!("fixtures/snap-list.txt"));
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0], package("core22", Some("20240111"), Some("latest/stable"), None, true));
+
+    let info = parse(Parser::Snap, PlsCommand::Info, include_str!("fixtures/snap-info.txt"));
+    assert_eq!(info, vec![package("vlc", Some("3.0.20-1-g2617de71b6"), Some("latest/stable"), Some("The ultimate media player"), true)]);
+}
+
+#[test]
+fn xbps() {
+    let search = parse(Parser::Xbps, PlsCommand::Search, include_str!("fixtures/xbps-search.txt"));
+    assert_eq!(search.len(), 3);
+    assert_eq!(search[0], package("vim", Some("9.0.1672_1"), None, Some("Vim editor (vi clone)"), true));
+    assert!(!search[1].installed);
+
+    let list = parse(Parser::Xbps, PlsCommand::List, include_str!("fixtures/xbps-list.txt"));
+    assert_eq!(list[0], package("base-files", Some("0.143_3"), None, Some("Void Linux base system files"), true));
+
+    let info = parse(Parser::Xbps, PlsCommand::Info, include_str!("fixtures/xbps-info.txt"));
+    assert_eq!(info, vec![package(
+        "vim",
+        Some("9.0.1672_1"),
+        Some("https://repo-default.voidlinux.org/current"),
+        Some("Vim editor (vi clone)"),
+        true,
+    )]);
+}
+
+#[test]
+fn zypper() {
+    let search = parse(Parser::Zypper, PlsCommand::Search, include_str!("fixtures/zypper-search.txt"));
+    assert_eq!(search, vec![
+        package("vim", None, None, Some("Vi IMproved"), true),
+        package("vim-data", None, None, Some("Data files needed by vim"), false),
+    ]);
+
+    let list = parse(Parser::Zypper, PlsCommand::List, include_str!("fixtures/zypper-list.txt"));
+    assert_eq!(list.len(), 2);
+    assert!(list.iter().all(|package| package.installed));
+
+    let info = parse(Parser::Zypper, PlsCommand::Info, include_str!("fixtures/zypper-info.txt"));
+    assert_eq!(info, vec![package("vim", Some("9.0.2103-1.1"), Some("Main Repository (OSS)"), Some("Vi IMproved"), true)]);
+}
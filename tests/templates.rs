@@ -0,0 +1,74 @@
+use please_install::{CommandLine, CustomVendor, PlsCommand, VendorBackend};
+use toml::Table;
+
+
+fn vendor(settings: &str) -> CustomVendor {
+    let table: Table = settings.parse().expect("settings should be valid TOML");
+    CustomVendor::from_table("mypm", &table).expect("vendor should be valid")
+}
+
+fn line(vendor: &CustomVendor, command: PlsCommand, args: &[&str], yes: bool) -> CommandLine {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    vendor.command_line(command, &args, yes).expect("command should be supported")
+}
+
+fn argv(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn args_expand_to_one_element_each() {
+    let mypm = vendor(r#"install = "mypm add $args""#);
+    let command = line(&mypm, PlsCommand::Install, &["vim", "two words", "a;rm -rf /"], false);
+    assert_eq!(command.program, "mypm");
+    assert_eq!(command.args, argv(&["add", "vim", "two words", "a;rm -rf /"]));
+
+    let command = line(&mypm, PlsCommand::Install, &[], false);
+    assert_eq!(command.args, argv(&["add"]));
+}
+
+#[test]
+fn quoted_args_stay_literal() {
+    let mypm = vendor(r#"files = "sh -c 'mypm files \"$1\" | sort' sh $args""#);
+    let command = line(&mypm, PlsCommand::Files, &["vim; reboot"], false);
+    assert_eq!(command.program, "sh");
+    assert_eq!(command.args, argv(&["-c", "mypm files \"$1\" | sort", "sh", "vim; reboot"]));
+
+    let mypm = vendor(r#"install = "mypm add '$args'""#);
+    let command = line(&mypm, PlsCommand::Install, &["vim"], false);
+    assert_eq!(command.args, argv(&["add", "$args"]));
+}
+
+#[test]
+fn yes_flag() {
+    let mypm = vendor(r#"
+        yes = ""
+        install = "mypm add $yes $args"
+    "#);
+    assert_eq!(line(&mypm, PlsCommand::Install, &["vim"], true).args, argv(&["add", "vim"]));
+
+    let mypm = vendor(r#"
+        yes = "--yes --quiet"
+        install = "mypm add $yes $args"
+        remove = "sh -c 'mypm del $yes \"$@\"' sh $args"
+    "#);
+    assert_eq!(line(&mypm, PlsCommand::Install, &["vim"], true).args, argv(&["add", "--yes", "--quiet", "vim"]));
+    assert_eq!(line(&mypm, PlsCommand::Install, &["vim"], false).args, argv(&["add", "vim"]));
+    assert_eq!(
+        line(&mypm, PlsCommand::Remove, &["vim"], true).args,
+        argv(&["-c", "mypm del --yes --quiet \"$@\"", "sh", "vim"]),
+    );
+}
+
+#[test]
+fn unbalanced_quotes_run_to_the_end() {
+    let mypm = vendor(r#"install = "mypm add 'unclosed $args""#);
+    assert_eq!(line(&mypm, PlsCommand::Install, &["vim"], false).args, argv(&["add", "unclosed $args"]));
+}
+
+#[test]
+fn display_quotes_for_the_shell() {
+    let mypm = vendor(r#"install = "mypm add $args""#);
+    let command = line(&mypm, PlsCommand::Install, &["vim", "it's", "a;b", "", "pkg=1.2*"], false);
+    assert_eq!(command.to_string(), r#"mypm add vim 'it'\''s' 'a;b' '' 'pkg=1.2*'"#);
+}
@@ -0,0 +1,92 @@
+use std::cmp::Ordering;
+use please_install::{compare, parse_requirement, split_version, Constraint, CustomVendor, Op, VendorBackend};
+use toml::Table;
+
+
+fn vendor(settings: &str) -> CustomVendor {
+    let table: Table = settings.parse().expect("settings should be valid TOML");
+    CustomVendor::from_table("mypm", &table).expect("vendor should be valid")
+}
+
+fn argv(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn constraint(op: Op, version: &str) -> Constraint {
+    Constraint { op, version: version.to_owned() }
+}
+
+#[test]
+fn split() {
+    assert_eq!(split_version("vim"), ("vim", ""));
+    assert_eq!(split_version("nodejs@18.19"), ("nodejs", "@18.19"));
+    assert_eq!(split_version("openssl>=3"), ("openssl", ">=3"));
+    assert_eq!(split_version("curl=7.88.1-10"), ("curl", "=7.88.1-10"));
+    assert_eq!(split_version("@types/node"), ("@types/node", ""));
+    assert_eq!(split_version("@types/node@18"), ("@types/node", "@18"));
+    assert_eq!(split_version("nodejs@"), ("nodejs", "@"));
+}
+
+#[test]
+fn requirements() {
+    assert_eq!(parse_requirement("@18.19"), Ok(constraint(Op::Prefix, "18.19")));
+    assert_eq!(parse_requirement("@>=18"), Ok(constraint(Op::Ge, "18")));
+    assert_eq!(parse_requirement(">=3"), Ok(constraint(Op::Ge, "3")));
+    assert_eq!(parse_requirement("==1.2"), Ok(constraint(Op::Eq, "1.2")));
+    assert_eq!(parse_requirement("=1.2"), Ok(constraint(Op::Eq, "1.2")));
+    assert_eq!(parse_requirement("<2"), Ok(constraint(Op::Lt, "2")));
+    assert_eq!(parse_requirement("@*"), Ok(constraint(Op::Any, "")));
+    assert!(parse_requirement("@").is_err());
+    assert!(parse_requirement(">=").is_err());
+    assert_eq!(constraint(Op::Ge, "3").requirement("openssl"), "openssl>=3");
+    assert_eq!(constraint(Op::Prefix, "18").requirement("nodejs"), "nodejs@18");
+    assert_eq!(constraint(Op::Any, "").requirement("git"), "git");
+}
+
+#[test]
+fn comparison() {
+    assert_eq!(compare("1.10", "1.9"), Ordering::Greater);
+    assert_eq!(compare("1.2", "1.2.0"), Ordering::Less);
+    assert_eq!(compare("2.0-rc1", "2.0-rc2"), Ordering::Less);
+    assert_eq!(compare("1:2.0", "2.0"), Ordering::Equal);
+    assert_eq!(compare("2:9.0.1378-2", "9.0.1378-1"), Ordering::Greater);
+}
+
+#[test]
+fn matching() {
+    let prefix = constraint(Op::Prefix, "18");
+    assert!(prefix.matches("18"));
+    assert!(prefix.matches("18.19.1"));
+    assert!(prefix.matches("1:18.2"));
+    assert!(!prefix.matches("180.1"));
+    assert!(!prefix.matches("18a"));
+    assert!(!prefix.matches("17.9"));
+
+    assert!(constraint(Op::Eq, "1.2").matches("1.2"));
+    assert!(!constraint(Op::Eq, "1.2").matches("1.2.1"));
+    assert!(constraint(Op::Ge, "3").matches("3.0.13"));
+    assert!(!constraint(Op::Ge, "3").matches("1.1.1w"));
+    assert!(constraint(Op::Lt, "2").matches("1.99"));
+    assert!(constraint(Op::Any, "").matches("anything"));
+}
+
+#[test]
+fn versioned() {
+    let mypm = vendor(r#"
+        [versions]
+        "=" = "$name==$version"
+        "@" = "$name --version $version*"
+    "#);
+    assert_eq!(
+        mypm.versioned(&argv(&["vim", "curl=7.88", "nodejs@18", "git@*"])).unwrap(),
+        argv(&["vim", "curl==7.88", "nodejs", "--version", "18*", "git"]),
+    );
+    let err = mypm.versioned(&argv(&["openssl>=3"])).unwrap_err().to_string();
+    assert_eq!(err, "openssl>=3: mypm only supports version requirements with @, =");
+    assert!(mypm.versioned(&argv(&["nodejs@"])).is_err());
+
+    let unversioned = vendor("");
+    let err = unversioned.versioned(&argv(&["nodejs@18"])).unwrap_err().to_string();
+    assert_eq!(err, "nodejs@18: mypm cannot install specific versions");
+    assert_eq!(unversioned.versioned(&argv(&["vim"])).unwrap(), argv(&["vim"]));
+}